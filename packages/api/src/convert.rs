@@ -0,0 +1,31 @@
+use crate::{
+    errors::to_py_err,
+    types::Type,
+    values::{to_py_object, to_wasm_value, Value},
+    wasmer_inner::wasmer,
+};
+use pyo3::{exceptions::PyTypeError, prelude::*};
+
+pub fn to_wasm(value: &PyAny, ty: Type) -> PyResult<Value> {
+    let ty: wasmer::Type = ty.into();
+
+    if !ty.is_num() {
+        return Err(to_py_err::<PyTypeError, _>(format!(
+            "Cannot convert a Python object to a `{:?}` value",
+            ty
+        )));
+    }
+
+    Ok(Value::raw_new(to_wasm_value((value, ty))?))
+}
+
+pub fn to_python(py: Python, value: &Value) -> PyResult<PyObject> {
+    if !value.inner().ty().is_num() {
+        return Err(to_py_err::<PyTypeError, _>(format!(
+            "Cannot convert a `{:?}` value to a Python object",
+            value.inner().ty()
+        )));
+    }
+
+    Ok(to_py_object(py)(value.inner()))
+}
@@ -1,6 +1,16 @@
-use pyo3::{prelude::*, type_object::PyTypeObject};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyInterruptedError, PyRuntimeError, PyTimeoutError},
+    prelude::*,
+    type_object::PyTypeObject,
+};
 use std::string::ToString;
 
+create_exception!(wasmer, HostTrap, PyRuntimeError);
+create_exception!(wasmer, WasiExit, PyException);
+create_exception!(wasmer, CallTimeoutError, PyTimeoutError);
+create_exception!(wasmer, CallInterruptedError, PyInterruptedError);
+
 pub fn to_py_err<PyError, Error>(error: Error) -> PyErr
 where
     PyError: PyTypeObject,
@@ -8,3 +18,12 @@ where
 {
     PyErr::new::<PyError, _>(error.to_string())
 }
+
+pub(crate) fn to_wasi_exit(py: Python, exit_code: u32) -> PyErr {
+    let error = WasiExit::new_err(format!("WASI exited with code: {}", exit_code));
+
+    // Setting an attribute on a fresh exception cannot fail.
+    let _ = error.instance(py).setattr("code", exit_code);
+
+    error
+}
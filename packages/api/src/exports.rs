@@ -5,9 +5,93 @@ use crate::{
 };
 use pyo3::{
     class::{basic::PyObjectProtocol, sequence::PySequenceProtocol},
+    conversion::IntoPy,
     exceptions::PyLookupError,
+    once_cell::GILOnceCell,
     prelude::*,
+    types::PyDict,
 };
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    slice,
+    sync::{atomic::AtomicUsize, Arc},
+};
+
+/// The common names of the exported allocator and deallocator
+/// functions, by order of preference, see `Exports.find_allocator`.
+const ALLOCATOR_CONVENTIONS: [(&str, &str); 4] = [
+    ("malloc", "free"),
+    ("__wbindgen_malloc", "__wbindgen_free"),
+    ("allocate", "deallocate"),
+    ("alloc", "dealloc"),
+];
+
+#[derive(Copy, Clone)]
+#[repr(u8)]
+pub enum ExportKind {
+    Function = 1,
+    Global = 2,
+    Memory = 3,
+    Table = 4,
+}
+
+impl ExportKind {
+    pub fn iter() -> slice::Iter<'static, ExportKind> {
+        static VARIANTS: [ExportKind; 4] = [
+            ExportKind::Function,
+            ExportKind::Global,
+            ExportKind::Memory,
+            ExportKind::Table,
+        ];
+
+        VARIANTS.iter()
+    }
+}
+
+impl From<&ExportKind> for &'static str {
+    fn from(value: &ExportKind) -> Self {
+        match value {
+            ExportKind::Function => "FUNCTION",
+            ExportKind::Global => "GLOBAL",
+            ExportKind::Memory => "MEMORY",
+            ExportKind::Table => "TABLE",
+        }
+    }
+}
+
+impl From<&wasmer::Extern> for ExportKind {
+    fn from(value: &wasmer::Extern) -> Self {
+        match value {
+            wasmer::Extern::Function(_) => Self::Function,
+            wasmer::Extern::Global(_) => Self::Global,
+            wasmer::Extern::Memory(_) => Self::Memory,
+            wasmer::Extern::Table(_) => Self::Table,
+        }
+    }
+}
+
+/// The Python `ExportKind` enum, set when the `wasmer` module is
+/// created, see `types::TYPE_ENUM`.
+pub(crate) static EXPORT_KIND_ENUM: GILOnceCell<PyObject> = GILOnceCell::new();
+
+impl ToPyObject for ExportKind {
+    fn to_object(&self, py: Python) -> PyObject {
+        let value = *self as u8;
+
+        EXPORT_KIND_ENUM
+            .get(py)
+            .and_then(|export_kind_enum| export_kind_enum.call1(py, (value,)).ok())
+            .unwrap_or_else(|| value.into_py(py))
+    }
+}
+
+impl IntoPy<PyObject> for ExportKind {
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
 
 /// Represents all the exports of an instance. It is built by
 /// `Instance.exports`.
@@ -38,30 +122,108 @@ use pyo3::{
 /// assert isinstance(exports.tab, Table)
 /// assert isinstance(exports.mem, Memory)
 /// ```
+///
+/// The representation of the exports lists their names, in the order
+/// they are declared by the module:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance
+///
+/// module = Module(
+///     Store(),
+///     """
+///     (module
+///       (func (export "sum"))
+///       (func (export "multiply")))
+///     """
+/// )
+/// instance = Instance(module)
+///
+/// assert repr(instance.exports) == 'Exports([sum, multiply])'
+/// ```
+///
+/// Accessing the same export twice returns the same object:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance
+///
+/// module = Module(Store(), '(module (func (export "sum")))')
+/// instance = Instance(module)
+///
+/// assert instance.exports.sum is instance.exports.sum
+/// ```
 #[pyclass(unsendable)]
 #[derive(Clone)]
 pub struct Exports {
     inner: wasmer::Exports,
+    /// The values of the exported globals when the exports are
+    /// created, i.e. at instantiation, so that they can be reset.
+    initial_global_values: HashMap<String, wasmer::Value>,
+    /// The Python objects already built for the exports, so that
+    /// accessing an export always returns the same object.
+    cache: RefCell<HashMap<String, PyObject>>,
+    /// Whether the exported functions are strictly marshaled.
+    strict_marshaling: bool,
+    /// Where the exported functions report the peak stack usage of
+    /// their calls, see `Instance.last_call_stack_usage`.
+    stack_usage: Rc<Cell<Option<usize>>>,
+    /// The number of calls to `Store.interrupt` of the store, shared
+    /// with the store.
+    interrupts: Arc<AtomicUsize>,
 }
 
 impl Exports {
-    pub fn new(inner: wasmer::Exports) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: wasmer::Exports,
+        strict_marshaling: bool,
+        stack_usage: Rc<Cell<Option<usize>>>,
+        interrupts: Arc<AtomicUsize>,
+    ) -> Self {
+        let initial_global_values = inner
+            .iter()
+            .filter_map(|(name, export)| match export {
+                wasmer::Extern::Global(global) => Some((name.clone(), global.get())),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            inner,
+            initial_global_values,
+            cache: RefCell::new(HashMap::new()),
+            strict_marshaling,
+            stack_usage,
+            interrupts,
+        }
     }
-}
 
-#[pyproto]
-impl PyObjectProtocol for Exports {
-    fn __getattr__(&self, key: String) -> PyResult<PyObject> {
-        let gil_guard = Python::acquire_gil();
-        let py = gil_guard.python();
+    /// Returns the Python object of the export named `key`, built
+    /// once and then cached.
+    pub(crate) fn get(&self, py: Python, key: String) -> PyResult<PyObject> {
+        if let Some(export) = self.cache.borrow().get(&key) {
+            return Ok(export.clone_ref(py));
+        }
 
-        Ok(match self.inner.get_extern(key.as_str()) {
-            Some(wasmer::Extern::Function(function)) => {
-                Py::new(py, Function::raw_new(function.clone()))?.to_object(py)
-            }
+        let export = match self.inner.get_extern(key.as_str()) {
+            Some(wasmer::Extern::Function(function)) => Py::new(
+                py,
+                Function::raw_new(
+                    function.clone(),
+                    key.clone(),
+                    self.strict_marshaling,
+                    Some(self.stack_usage.clone()),
+                    Some(self.interrupts.clone()),
+                ),
+            )?
+            .to_object(py),
             Some(wasmer::Extern::Global(global)) => {
-                Py::new(py, Global::raw_new(global.clone()))?.to_object(py)
+                let initial_value = self
+                    .initial_global_values
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or_else(|| global.get());
+
+                Py::new(py, Global::raw_new(global.clone(), initial_value))?.to_object(py)
             }
             Some(wasmer::Extern::Memory(memory)) => {
                 Py::new(py, Memory::raw_new(memory.clone()))?.to_object(py)
@@ -75,7 +237,212 @@ impl PyObjectProtocol for Exports {
                     key
                 )))
             }
-        })
+        };
+
+        self.cache.borrow_mut().insert(key, export.clone_ref(py));
+
+        Ok(export)
+    }
+
+    /// Returns the names of the first exported allocator and
+    /// deallocator pair following a common convention, see
+    /// `Exports.find_allocator`.
+    pub(crate) fn allocator_names(&self) -> Option<(&'static str, &'static str)> {
+        let has_type = |name: &str, results: &[wasmer::Type]| match self.inner.get_function(name) {
+            Ok(function) => {
+                let function_type = function.ty();
+
+                function_type.params() == [wasmer::Type::I32] && function_type.results() == results
+            }
+            Err(_) => false,
+        };
+
+        ALLOCATOR_CONVENTIONS
+            .iter()
+            .find(|(allocator, deallocator)| {
+                has_type(allocator, &[wasmer::Type::I32]) && has_type(deallocator, &[])
+            })
+            .copied()
+    }
+}
+
+#[pymethods]
+impl Exports {
+    /// Counts the exports by kind. It returns a dictionary where keys
+    /// are `ExportKind` and values are the number of exports of this
+    /// kind. All kinds are present, even when the count is zero.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, ExportKind
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "f1"))
+    ///       (func (export "f2"))
+    ///       (func (export "f3"))
+    ///       (memory (export "mem") 1))
+    ///     """
+    /// )
+    /// exports = Instance(module).exports
+    ///
+    /// assert len(exports) == 4
+    ///
+    /// counts = exports.counts()
+    ///
+    /// assert counts[ExportKind.FUNCTION] == 3
+    /// assert counts[ExportKind.MEMORY] == 1
+    /// assert counts[ExportKind.GLOBAL] == 0
+    /// assert counts[ExportKind.TABLE] == 0
+    /// ```
+    #[text_signature = "($self)"]
+    fn counts<'p>(&self, py: Python<'p>) -> PyResult<&'p PyDict> {
+        let mut counts = [0usize; 4];
+
+        for (_, export) in self.inner.iter() {
+            counts[ExportKind::from(export) as usize - 1] += 1;
+        }
+
+        let py_counts = PyDict::new(py);
+
+        for (kind, count) in ExportKind::iter().zip(counts.iter()) {
+            py_counts.set_item(kind, count)?;
+        }
+
+        Ok(py_counts)
+    }
+
+    /// Returns a dictionary of the exported functions whose names
+    /// start with `prefix`, in the order they are declared by the
+    /// module. It is handy to discover a family of functions exported
+    /// by convention, e.g. by a plugin.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "plugin_init") (result i32) i32.const 1)
+    ///       (func (export "plugin_run") (result i32) i32.const 2)
+    ///       (func (export "main"))
+    ///       (global (export "plugin_version") i32 (i32.const 3)))
+    ///     """
+    /// )
+    /// exports = Instance(module).exports
+    /// plugins = exports.matching('plugin_')
+    ///
+    /// assert list(plugins.keys()) == ['plugin_init', 'plugin_run']
+    /// assert plugins['plugin_run']() == 2
+    /// ```
+    #[text_signature = "($self, prefix)"]
+    fn matching<'p>(&self, py: Python<'p>, prefix: &str) -> PyResult<&'p PyDict> {
+        let functions = PyDict::new(py);
+
+        for (name, export) in self.inner.iter() {
+            if name.starts_with(prefix) {
+                if let wasmer::Extern::Function(_) = export {
+                    functions.set_item(name, self.get(py, name.clone())?)?;
+                }
+            }
+        }
+
+        Ok(functions)
+    }
+
+    /// Looks for an exported allocator and deallocator pair following
+    /// a common convention, and returns their `(allocator,
+    /// deallocator)` names, or `None` if there is none. The known
+    /// pairs are, by order of preference: `malloc`/`free`,
+    /// `__wbindgen_malloc`/`__wbindgen_free`, `allocate`/`deallocate`
+    /// and `alloc`/`dealloc`.
+    ///
+    /// It is a heuristic: the allocator must be a function of type
+    /// `(i32) -> i32` (a size to a pointer), and the deallocator of
+    /// type `(i32) -> ()` (a pointer). The pair found is used by
+    /// default by `Instance.pass_string` and `Instance.free_string`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "__wbindgen_malloc") (param i32) (result i32)
+    ///         i32.const 16)
+    ///       (func (export "__wbindgen_free") (param i32)))
+    ///     """
+    /// )
+    /// exports = Instance(module).exports
+    ///
+    /// assert exports.find_allocator() == ('__wbindgen_malloc', '__wbindgen_free')
+    /// assert Instance(Module(Store(), '(module)')).exports.find_allocator() == None
+    /// ```
+    #[text_signature = "($self)"]
+    fn find_allocator(&self) -> Option<(&'static str, &'static str)> {
+        self.allocator_names()
+    }
+
+    /// Returns the kind of an export, as an `ExportKind`, without
+    /// building the export itself. It raises a `LookupError` if the
+    /// export does not exist.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, ExportKind
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "func"))
+    ///       (memory (export "mem") 1))
+    ///     """
+    /// )
+    /// exports = Instance(module).exports
+    ///
+    /// assert exports.kind_of("func") == ExportKind.FUNCTION
+    /// assert exports.kind_of("mem") == ExportKind.MEMORY
+    /// ```
+    #[text_signature = "($self, name)"]
+    fn kind_of(&self, name: &str) -> PyResult<ExportKind> {
+        self.inner
+            .get_extern(name)
+            .map(ExportKind::from)
+            .ok_or_else(|| {
+                to_py_err::<PyLookupError, _>(format!("Export `{}` does not exist.", name))
+            })
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for Exports {
+    fn __getattr__(&self, key: String) -> PyResult<PyObject> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        self.get(py, key)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Exports([{}])",
+            self.inner
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )
     }
 }
 
@@ -1,16 +1,25 @@
 use crate::{
-    errors::to_py_err,
+    errors::{to_py_err, to_wasi_exit, CallInterruptedError, CallTimeoutError, HostTrap},
     store::Store,
-    types::FunctionType,
-    values::{to_py_object, to_wasm_value},
-    wasmer_inner::wasmer,
+    types::{FunctionType, Type},
+    values::{is_strictly_of_type, python_type_name, to_py_object, to_wasm_value, Value},
+    wasmer_inner::{wasmer, wasmer_vm, wasmer_wasi},
 };
 use pyo3::{
-    exceptions::{PyRuntimeError, PyValueError},
+    exceptions::{PyOverflowError, PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyDict, PyTuple},
+    types::{PyDict, PyList, PyTuple},
+};
+use std::{
+    cell::{Cell, RefCell},
+    io,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
-use std::{io, sync::Arc};
 
 /// Represents a WebAssembly function instance.
 ///
@@ -56,39 +65,427 @@ use std::{io, sync::Arc};
 /// store = Store()
 /// function = Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I32]))
 /// ```
+///
+/// If the function is annotated _and_ a `FunctionType` is given, the
+/// annotations must match the function type, parameter by parameter,
+/// otherwise a `ValueError` is raised. An `int` annotation matches
+/// `I32` and `I64`, and a `float` annotation matches `F32` and
+/// `F64`:
+///
+/// ```py
+/// from wasmer import Store, Function, FunctionType, Type
+///
+/// def half(x: float) -> float:
+///     return x / 2
+///
+/// store = Store()
+///
+/// # `float` is compatible with `F32` and `F64`, but not with `I32`.
+/// Function(store, half, FunctionType([Type.F64], [Type.F64]))
+///
+/// try:
+///     Function(store, half, FunctionType([Type.I32], [Type.F64]))
+/// except ValueError:
+///     pass
+/// else:
+///     assert False
+/// ```
+///
+/// A host function can be flagged as deterministic with
+/// `deterministic=True`, i.e. it always returns the same results for
+/// the same arguments, and has no side-effect. The flag is only
+/// informative for now: Wasmer does not memoize the function, but it
+/// lets tools reason about reproducibility. See `Function.deterministic`.
+///
+/// A raw host function, created with `raw=True` and a
+/// `FunctionType`, receives its arguments as a single list of
+/// `Value` objects, that preserve the exact WebAssembly types, and
+/// may return `Value` objects (or Python numbers). It is handy for a
+/// generic proxy or tracer that does not care about the types:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Function, FunctionType, Type
+///
+/// def trace(arguments):
+///     from wasmer import Value
+///
+///     print([(argument.type, argument.value) for argument in arguments])
+///
+///     return Value.i64(arguments[0].value + int(arguments[1].value))
+///
+/// store = Store()
+/// function = Function(store, trace, FunctionType([Type.I32, Type.F64], [Type.I64]), raw=True)
+///
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "env" "trace" (func $trace (param i32 f64) (result i64)))
+///       (func (export "run") (result i64)
+///         (call $trace (i32.const 1) (f64.const 2.5))))
+///     """
+/// )
+///
+/// import_object = ImportObject()
+/// import_object.register("env", {"trace": function})
+///
+/// assert Instance(module, import_object).exports.run() == 3
+/// ```
+///
+/// A host function can deliberately trap the WebAssembly caller by
+/// raising a `HostTrap` exception. Contrary to any other exception
+/// (that is turned into a `RuntimeError`), the `HostTrap` exception
+/// is re-raised as is to the host caller, with its message and
+/// arguments, e.g. a custom code:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Function, HostTrap
+///
+/// def abort():
+///     from wasmer import HostTrap
+///
+///     raise HostTrap('aborted by the host', 42)
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "env" "abort" (func $abort))
+///       (func (export "run")
+///         call $abort))
+///     """
+/// )
+///
+/// import_object = ImportObject()
+/// import_object.register("env", {"abort": Function(store, abort)})
+///
+/// instance = Instance(module, import_object)
+///
+/// try:
+///     instance.exports.run()
+/// except HostTrap as trap:
+///     assert trap.args == ('aborted by the host', 42)
+/// else:
+///     assert False
+/// ```
 #[pyclass(unsendable)]
-#[text_signature = "(store, function, function_type)"]
+#[text_signature = "(store, function, function_type, deterministic, raw)"]
 pub struct Function {
     inner: wasmer::Function,
+    /// The export name, or the name of the Python function for a
+    /// host function.
+    name: String,
+    deterministic: bool,
+    /// Whether the arguments are strictly marshaled, see
+    /// `Store(strict_marshaling=True)`.
+    strict_marshaling: bool,
+    /// For a function returned by `Function.partial`, the original
+    /// function and the bound leading arguments. Wasmer cannot call
+    /// a host function from the host, so the original function is
+    /// called directly.
+    partial: Option<(wasmer::Function, Vec<wasmer::Value>)>,
+    /// For an exported function, where to report the peak stack
+    /// usage of its calls, see `Instance.last_call_stack_usage`.
+    stack_usage: Option<Rc<Cell<Option<usize>>>>,
+    /// For an exported function, the number of calls to
+    /// `Store.interrupt` of its store.
+    interrupts: Option<Arc<AtomicUsize>>,
+}
+
+thread_local! {
+    /// The earliest deadline of the calls in progress, see the
+    /// `timeout` and `deadline` arguments of `Function.__call__`.
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+
+    /// The stack address when the outermost call started, and the
+    /// peak stack usage since then, in bytes, if measured.
+    static STACK_USAGE: Cell<Option<(usize, Option<usize>)>> = const { Cell::new(None) };
+
+    /// The number of calls to `Store.interrupt` of the stores of the
+    /// calls in progress, and their values when the calls started.
+    static INTERRUPTS: RefCell<Vec<(Arc<AtomicUsize>, usize)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the address of the current top of the stack, roughly.
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+
+    &marker as *const u8 as usize
+}
+
+/// Measures the stack usage of the outermost call in progress, if
+/// any. It is called by the host functions, since Wasmer cannot
+/// inspect the stack of running WebAssembly code.
+fn measure_stack_usage() {
+    STACK_USAGE.with(|stack_usage| {
+        if let Some((base, peak)) = stack_usage.get() {
+            // The stack grows downward on all the supported platforms.
+            let usage = base.saturating_sub(stack_address());
+
+            stack_usage.set(Some((
+                base,
+                Some(peak.map_or(usage, |peak| peak.max(usage))),
+            )));
+        }
+    })
+}
+
+/// Checks whether the earliest deadline of the calls in progress, if
+/// any, is exceeded.
+fn is_deadline_exceeded() -> bool {
+    matches!(DEADLINE.with(Cell::get), Some(deadline) if Instant::now() >= deadline)
+}
+
+fn deadline_exceeded_error() -> PyErr {
+    to_py_err::<CallTimeoutError, _>("The call has exceeded its deadline")
+}
+
+/// Checks whether one of the calls in progress, if any, has been
+/// interrupted with `Store.interrupt`.
+fn is_interrupted() -> bool {
+    INTERRUPTS.with(|calls| {
+        calls
+            .borrow()
+            .iter()
+            .any(|(interrupts, started_at)| interrupts.load(Ordering::SeqCst) != *started_at)
+    })
+}
+
+fn interrupted_error() -> PyErr {
+    to_py_err::<CallInterruptedError, _>("The call has been interrupted by `Store.interrupt`")
 }
 
 impl Function {
-    pub fn raw_new(inner: wasmer::Function) -> Self {
-        Self { inner }
+    pub fn raw_new(
+        inner: wasmer::Function,
+        name: String,
+        strict_marshaling: bool,
+        stack_usage: Option<Rc<Cell<Option<usize>>>>,
+        interrupts: Option<Arc<AtomicUsize>>,
+    ) -> Self {
+        Self {
+            inner,
+            name,
+            deterministic: false,
+            strict_marshaling,
+            partial: None,
+            stack_usage,
+            interrupts,
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Function {
         &self.inner
     }
+
+    /// Converts the Python arguments to WebAssembly values of the
+    /// given parameter types.
+    fn to_wasm_arguments(
+        &self,
+        py: Python,
+        arguments: &PyTuple,
+        params: &[wasmer::Type],
+    ) -> PyResult<Vec<wasmer::Value>> {
+        // The number of arguments is checked first, since zipping the
+        // arguments with the parameters drops the surplus ones.
+        if arguments.len() != params.len() {
+            return Err(to_py_err::<PyRuntimeError, _>(format!(
+                "The function expects {} argument(s), got {} (parameters of the function: {:?})",
+                params.len(),
+                arguments.len(),
+                params
+            )));
+        }
+
+        arguments
+            .iter()
+            .zip(params.iter().cloned())
+            .enumerate()
+            .map(|(nth, (argument, ty))| {
+                if let Ok(value) = argument.downcast::<PyCell<Value>>() {
+                    let value = value.borrow().inner().clone();
+
+                    return if value.ty() == ty {
+                        Ok(value)
+                    } else {
+                        Err(to_py_err::<PyTypeError, _>(format!(
+                            "Argument #{} must be a value of type `{:?}`, got `{:?}`",
+                            nth,
+                            ty,
+                            value.ty()
+                        )))
+                    };
+                }
+
+                if self.strict_marshaling && !is_strictly_of_type(argument, ty) {
+                    return Err(to_py_err::<PyTypeError, _>(format!(
+                        "Argument #{} (`{}`) must be of type `{}` for the `{:?}` parameter, got `{}` (strict marshaling)",
+                        nth,
+                        argument,
+                        python_type_name(ty),
+                        ty,
+                        argument.get_type().name()?
+                    )));
+                }
+
+                to_wasm_value((argument, ty)).map_err(|error| {
+                    if error.is_instance::<PyOverflowError>(py) {
+                        to_py_err::<PyOverflowError, _>(format!(
+                            "Argument #{} (`{}`) overflows the `{:?}` type",
+                            nth, argument, ty
+                        ))
+                    } else {
+                        error
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Calls the function like `Function.call`, under the given
+    /// deadline, if any.
+    fn call_with_deadline(
+        &self,
+        py: Python,
+        arguments: &PyTuple,
+        deadline: Option<Instant>,
+    ) -> PyResult<PyObject> {
+        let deadline = match deadline {
+            Some(deadline) => deadline,
+            None => return self.call(py, arguments),
+        };
+
+        let previous_deadline = DEADLINE.with(Cell::get);
+        DEADLINE.with(|current_deadline| {
+            current_deadline.set(Some(match previous_deadline {
+                Some(previous_deadline) => previous_deadline.min(deadline),
+                None => deadline,
+            }))
+        });
+
+        let result = if is_deadline_exceeded() {
+            Err(deadline_exceeded_error())
+        } else {
+            self.call(py, arguments)
+        };
+
+        DEADLINE.with(|current_deadline| current_deadline.set(previous_deadline));
+
+        result
+    }
+
+    /// Calls the function with Python arguments, checking their
+    /// number and types, and returns its results as Python values.
+    pub(crate) fn call(&self, py: Python, arguments: &PyTuple) -> PyResult<PyObject> {
+        let arguments = self.to_wasm_arguments(py, arguments, self.inner.ty().params())?;
+
+        // Only the outermost call measures its stack usage.
+        let is_outermost_call = STACK_USAGE.with(|stack_usage| {
+            if stack_usage.get().is_some() {
+                return false;
+            }
+
+            stack_usage.set(Some((stack_address(), None)));
+
+            true
+        });
+
+        if let Some(interrupts) = &self.interrupts {
+            INTERRUPTS.with(|calls| {
+                calls
+                    .borrow_mut()
+                    .push((interrupts.clone(), interrupts.load(Ordering::SeqCst)))
+            });
+        }
+
+        let results = match &self.partial {
+            Some((function, fixed_arguments)) => function.call(
+                &fixed_arguments
+                    .iter()
+                    .cloned()
+                    .chain(arguments)
+                    .collect::<Vec<_>>(),
+            ),
+            None => self.inner.call(&arguments),
+        }
+        .map(<[_]>::into_vec)
+        // A `HostTrap` raised by a host function is carried as is
+        // by the runtime error, so let's re-raise it. A WASI
+        // program calling `proc_exit` is raised as `WasiExit`.
+        .map_err(|error| match error.downcast::<PyErr>() {
+            Ok(error) => error,
+            Err(error) => match error.downcast::<wasmer_wasi::WasiError>() {
+                Ok(wasmer_wasi::WasiError::Exit(exit_code)) => to_wasi_exit(py, exit_code),
+                Ok(error) => to_py_err::<PyRuntimeError, _>(error),
+                Err(error) => to_py_err::<PyRuntimeError, _>(error),
+            },
+        });
+
+        if self.interrupts.is_some() {
+            INTERRUPTS.with(|calls| calls.borrow_mut().pop());
+        }
+
+        if is_outermost_call {
+            let peak = STACK_USAGE
+                .with(|stack_usage| stack_usage.take())
+                .and_then(|(_, peak)| peak);
+
+            if let Some(stack_usage) = &self.stack_usage {
+                stack_usage.set(peak);
+            }
+        }
+
+        let results = results?;
+
+        let to_py_object = to_py_object(py);
+
+        Ok(match results.len() {
+            0 => py.None(),
+            1 => to_py_object(&results[0]),
+            _ => PyTuple::new(
+                py,
+                results.iter().map(to_py_object).collect::<Vec<PyObject>>(),
+            )
+            .to_object(py),
+        })
+    }
 }
 
 #[pymethods]
 impl Function {
     #[new]
-    fn new(
+    #[args(function_type = "None", deterministic = "false", raw = "false")]
+    pub(crate) fn new(
         py: Python,
         store: &Store,
         py_function: &PyAny,
         function_type: Option<&FunctionType>,
+        deterministic: bool,
+        raw: bool,
     ) -> PyResult<Self> {
         if !py_function.is_callable() {
             return Err(to_py_err::<PyValueError, _>("Function must be a callable"));
         }
 
+        if raw && function_type.is_none() {
+            return Err(to_py_err::<PyValueError, _>(
+                "A raw function (`raw=True`) must be given a `FunctionType`",
+            ));
+        }
+
         let (argument_types, result_types) = match function_type {
             Some(function_type) => {
                 let function_type: wasmer::FunctionType = function_type.into();
 
+                // When the function is also annotated, the annotations
+                // must agree with the function type. The ones of a raw
+                // function, that receives a single list, are ignored.
+                if !raw && py_function.hasattr("__annotations__")? {
+                    check_annotations(py, py_function, &function_type)?;
+                }
+
                 (
                     function_type.params().to_vec(),
                     function_type.results().to_vec(),
@@ -102,35 +499,7 @@ impl Function {
                     ));
                 }
 
-                let annotations = py_function
-                    .getattr("__annotations__")?
-                    .downcast::<PyDict>()
-                    .map_err(PyErr::from)?;
-
-                let mut argument_types = Vec::new();
-                let mut result_types = Vec::new();
-
-                for (annotation_name, annotation_value) in annotations {
-                    let ty = match annotation_value.to_string().as_str() {
-                        "i32" | "I32" | "<class 'int'>" => wasmer::Type::I32,
-                        "i64" | "I64" => wasmer::Type::I64,
-                        "f32" | "F32" | "<class 'float'>" => wasmer::Type::F32,
-                        "f64" | "F64" => wasmer::Type::F64,
-                        ty => {
-                            return Err(to_py_err::<PyRuntimeError, _>(format!(
-                                "Type `{}` is not a supported type",
-                                ty,
-                            )))
-                        }
-                    };
-
-                    match annotation_name.to_string().as_str() {
-                        "return" => result_types.push(ty),
-                        _ => argument_types.push(ty),
-                    }
-                }
-
-                (argument_types, result_types)
+                types_from_annotations(py, py_function)?
             }
         };
 
@@ -138,15 +507,19 @@ impl Function {
         struct Environment {
             py_function: Arc<PyObject>,
             result_types: Vec<wasmer::Type>,
+            strict_marshaling: bool,
+            raw: bool,
         }
 
         let environment = Environment {
             py_function: Arc::new(py_function.to_object(py)),
             result_types: result_types.clone(),
+            strict_marshaling: store.strict_marshaling(),
+            raw,
         };
 
         let host_function = wasmer::Function::new_with_env(
-            store.inner(),
+            store.inner()?,
             &wasmer::FunctionType::new(argument_types, result_types),
             environment,
             |environment,
@@ -155,19 +528,72 @@ impl Function {
                 let gil = Python::acquire_gil();
                 let py = gil.python();
 
+                measure_stack_usage();
+
+                // The host function is not called once the deadline
+                // of the call in progress is exceeded, see
+                // `Function.__call__`.
+                if is_deadline_exceeded() {
+                    return Err(wasmer::RuntimeError::from_trap(wasmer_vm::Trap::User(
+                        Box::new(deadline_exceeded_error()),
+                    )));
+                }
+
+                // Same for a call interrupted by `Store.interrupt`.
+                if is_interrupted() {
+                    return Err(wasmer::RuntimeError::from_trap(wasmer_vm::Trap::User(
+                        Box::new(interrupted_error()),
+                    )));
+                }
+
+                if environment.raw {
+                    return call_raw_host_function(
+                        py,
+                        &environment.py_function,
+                        arguments,
+                        &environment.result_types,
+                        environment.strict_marshaling,
+                    );
+                }
+
                 let to_py_object = to_py_object(py);
                 let arguments: Vec<PyObject> = arguments.iter().map(to_py_object).collect();
 
                 let results = environment
                     .py_function
                     .call(py, PyTuple::new(py, arguments), None)
-                    .map_err(|error| {
-                        wasmer::RuntimeError::new(io::Error::from(error).to_string())
-                    })?;
+                    .map_err(|error| to_runtime_error(py, error))?;
 
                 let result_types = environment.result_types.clone();
                 let has_result_types = !result_types.is_empty();
 
+                // With strict marshaling, a result of the wrong type
+                // is raised as a `TypeError` by the caller.
+                if environment.strict_marshaling {
+                    let results = results.as_ref(py);
+                    let results = match results.downcast::<PyTuple>() {
+                        Ok(results) => results.as_slice().to_vec(),
+                        Err(_) => vec![results],
+                    };
+
+                    for (nth, (result, ty)) in results.iter().zip(result_types.iter()).enumerate() {
+                        if !is_strictly_of_type(result, *ty) {
+                            let error = to_py_err::<PyTypeError, _>(format!(
+                                "Result #{} (`{}`) must be of type `{}` for the `{:?}` result, got `{}` (strict marshaling)",
+                                nth,
+                                result,
+                                python_type_name(*ty),
+                                ty,
+                                result.get_type().name().unwrap_or("unknown"),
+                            ));
+
+                            return Err(wasmer::RuntimeError::from_trap(wasmer_vm::Trap::User(
+                                Box::new(error),
+                            )));
+                        }
+                    }
+                }
+
                 Ok(if let Ok(results) = results.cast_as::<PyTuple>(py) {
                     results
                         .iter()
@@ -196,35 +622,443 @@ impl Function {
             },
         );
 
-        Ok(Self::raw_new(host_function))
+        // The name of the Python function, or of the type of the
+        // callable object, e.g. for a `functools.partial`.
+        let name = match py_function.getattr("__name__") {
+            Ok(name) => name.to_string(),
+            Err(_) => py_function.get_type().name()?.to_string(),
+        };
+
+        Ok(Self {
+            inner: host_function,
+            name,
+            deterministic,
+            strict_marshaling: store.strict_marshaling(),
+            partial: None,
+            stack_usage: None,
+            interrupts: None,
+        })
     }
 
     /// Calls the function as a regular Python function.
+    ///
+    /// The optional `expected_results` keyword argument is a list of
+    /// `Type`. If given, the result types of the function are checked
+    /// against it before calling, and a `TypeError` is raised if they
+    /// differ. It is handy to fail fast when a function reference is
+    /// kept around and the module may have changed.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Type
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// sum = Instance(module).exports.sum
+    ///
+    /// assert sum(1, 2, expected_results=[Type.I32]) == 3
+    ///
+    /// try:
+    ///     sum(1, 2, expected_results=[Type.I64])
+    /// except TypeError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    ///
+    /// An argument can be a `Value`, to give its WebAssembly type
+    /// explicitly. It is never converted to another type: if its type
+    /// is not the type of the parameter, a `TypeError` is raised.
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Value
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "double") (param i64) (result i64)
+    ///         local.get 0
+    ///         i64.const 2
+    ///         i64.mul))
+    ///     """
+    /// )
+    /// double = Instance(module).exports.double
+    ///
+    /// assert double(Value.i64(21)) == 42
+    ///
+    /// try:
+    ///     double(Value.i32(21))
+    /// except TypeError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    ///
+    /// The optional `timeout` keyword argument is a number of
+    /// seconds, and the optional `deadline` keyword argument is an
+    /// absolute time, as returned by `time.monotonic()`. A deadline
+    /// can be shared by several calls, to give them an overall
+    /// budget; one too far in the future to be represented is
+    /// ignored. When the deadline is exceeded before the call starts,
+    /// or when the WebAssembly code calls a host function after the
+    /// deadline, a `CallTimeoutError` (a `TimeoutError`) is raised
+    /// and the host function is not called.
+    ///
+    /// Wasmer cannot interrupt running WebAssembly code, so the
+    /// deadline is only enforced at these points, like
+    /// `Store.interrupt`. A loop that does not call the host cannot
+    /// be interrupted, and a call that completes without calling the
+    /// host after the deadline returns its results as usual.
+    ///
+    /// ```py
+    /// import time
+    /// from wasmer import Store, Module, Instance, CallTimeoutError
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// sum = Instance(module).exports.sum
+    /// deadline = time.monotonic() + 60
+    ///
+    /// assert sum(1, 2, deadline=deadline) == 3
+    /// assert sum(3, 4, deadline=deadline) == 7
+    /// assert sum(5, 6, timeout=60) == 11
+    ///
+    /// try:
+    ///     sum(1, 2, deadline=time.monotonic() - 1)
+    /// except CallTimeoutError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
     #[call]
+    #[args(arguments = "*", keyword_arguments = "**")]
+    fn __call__(
+        &self,
+        py: Python,
+        arguments: &PyTuple,
+        keyword_arguments: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        let mut expected_results = None;
+        let mut deadline: Option<Instant> = None;
+
+        if let Some(keyword_arguments) = keyword_arguments {
+            for (name, value) in keyword_arguments {
+                let name = name.extract::<&str>()?;
+
+                match name {
+                    "expected_results" => expected_results = Some(value.extract::<Vec<Type>>()?),
+                    "timeout" | "deadline" => {
+                        if value.is_none() {
+                            continue;
+                        }
+
+                        // A deadline is converted to a timeout, since
+                        // it is relative to `time.monotonic()`.
+                        let mut timeout = value.extract::<f64>()?;
+
+                        if name == "deadline" {
+                            timeout -= py
+                                .import("time")?
+                                .call_method0("monotonic")?
+                                .extract::<f64>()?;
+                        }
+
+                        if timeout.is_nan() || timeout.is_infinite() {
+                            return Err(to_py_err::<PyValueError, _>(format!(
+                                "The `{}` argument must be a finite number of seconds, given `{}`",
+                                name, value
+                            )));
+                        }
+
+                        // A deadline too far in the future to be
+                        // represented is no deadline at all.
+                        let call_deadline = match Duration::try_from_secs_f64(timeout.max(0.0))
+                            .ok()
+                            .and_then(|timeout| Instant::now().checked_add(timeout))
+                        {
+                            Some(call_deadline) => call_deadline,
+                            None => continue,
+                        };
+
+                        deadline = Some(match deadline {
+                            Some(deadline) => deadline.min(call_deadline),
+                            None => call_deadline,
+                        });
+                    }
+                    name => {
+                        return Err(to_py_err::<PyTypeError, _>(format!(
+                            "`Function.__call__` got an unexpected keyword argument `{}`",
+                            name
+                        )))
+                    }
+                }
+            }
+        }
+
+        if let Some(expected_results) = expected_results {
+            let function_type = self.inner.ty();
+            let results = function_type.results();
+            let expected_results = expected_results
+                .into_iter()
+                .map(Into::into)
+                .collect::<Vec<wasmer::Type>>();
+
+            if results != expected_results.as_slice() {
+                return Err(to_py_err::<PyTypeError, _>(format!(
+                    "The function results are `{:?}`, but `{:?}` were expected",
+                    results, expected_results
+                )));
+            }
+        }
+
+        self.call_with_deadline(py, arguments, deadline)
+    }
+
+    /// Calls the function with arguments given as a single list or
+    /// tuple. It is handy when the arguments are built dynamically.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (type (func (param i32 i32) (result i32)))
+    ///       (func (type 0)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add)
+    ///       (export "sum" (func 0)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// sum = instance.exports.sum
+    ///
+    /// assert sum.apply([1, 2]) == 3
+    /// assert sum.apply((1, 2)) == 3
+    /// ```
+    #[text_signature = "($self, arguments)"]
+    fn apply(&self, py: Python, arguments: &PyAny) -> PyResult<PyObject> {
+        // Only lists and tuples are accepted: other sequences, like
+        // `str` or `bytes`, would be silently split into arguments.
+        let arguments = if let Ok(arguments) = arguments.downcast::<PyTuple>() {
+            arguments
+        } else if let Ok(arguments) = arguments.downcast::<PyList>() {
+            PyTuple::new(py, arguments.iter().collect::<Vec<_>>())
+        } else {
+            return Err(to_py_err::<PyTypeError, _>(
+                "`Function.apply` expects a list or a tuple of arguments",
+            ));
+        };
+
+        self.call(py, arguments)
+    }
+
+    /// Checks the arguments like `Function.__call__` would, without
+    /// calling the function: the same exception is raised if the
+    /// number or the types of the arguments do not match the
+    /// function type, and `None` is returned otherwise. It lets a
+    /// host validate arguments given by a user before a call that
+    /// may have side effects.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// sum = Instance(module).exports.sum
+    ///
+    /// sum.check_args(1, 2)
+    ///
+    /// try:
+    ///     sum.check_args(1, 'two')
+    /// except TypeError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    #[text_signature = "($self, *arguments)"]
     #[args(arguments = "*")]
-    fn __call__<'p>(&self, py: Python<'p>, arguments: &PyTuple) -> PyResult<PyObject> {
-        let arguments: Vec<wasmer::Value> = arguments
+    fn check_args(&self, py: Python, arguments: &PyTuple) -> PyResult<()> {
+        self.to_wasm_arguments(py, arguments, self.inner.ty().params())?;
+
+        Ok(())
+    }
+
+    /// Calls the function like `Function.__call__`, and measures the
+    /// call. It returns a `(result, points_used, elapsed_ns)` tuple,
+    /// where `elapsed_ns` is the wall-clock time of the call, in
+    /// nanoseconds.
+    ///
+    /// `points_used` is always `None`: metering requires a middleware
+    /// to be compiled into the module, and the stores of this package
+    /// do not install any. It keeps its place in the tuple, so the
+    /// shape of the tuple will not change once metering is
+    /// supported.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// sum = Instance(module).exports.sum
+    ///
+    /// result, points_used, elapsed_ns = sum.call_with_metrics(1, 2)
+    ///
+    /// assert result == 3
+    /// assert points_used == None
+    /// assert elapsed_ns >= 0
+    /// ```
+    #[text_signature = "($self, *arguments)"]
+    #[args(arguments = "*")]
+    fn call_with_metrics(&self, py: Python, arguments: &PyTuple) -> PyResult<PyObject> {
+        let start = Instant::now();
+        let result = self.call(py, arguments)?;
+        let elapsed_ns = start.elapsed().as_nanos() as u64;
+
+        Ok((result, None::<u64>, elapsed_ns).to_object(py))
+    }
+
+    /// Binds the given leading arguments, like `functools.partial`,
+    /// and returns a new `Function` expecting the remaining
+    /// parameters only, as reported by its `type`. The bound
+    /// arguments are checked against their parameter types right
+    /// away, and the remaining ones when the new function is called.
+    ///
+    /// The new function is a genuine `Function`: it can be called,
+    /// but also imported by another module, or stored in a `Table`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Type
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// sum = Instance(module).exports.sum
+    /// increment = sum.partial(1)
+    ///
+    /// assert increment(41) == 42
+    /// assert increment.type.params == [Type.I32]
+    /// assert increment.type.results == [Type.I32]
+    /// ```
+    #[args(fixed_arguments = "*")]
+    #[text_signature = "($self, *fixed_arguments)"]
+    fn partial(&self, py: Python, fixed_arguments: &PyTuple) -> PyResult<Self> {
+        let function_type = self.inner.ty();
+        let params = function_type.params();
+
+        if fixed_arguments.len() > params.len() {
+            return Err(to_py_err::<PyTypeError, _>(format!(
+                "`Function.partial` got {} argument(s) to bind, but the function has only {} parameter(s)",
+                fixed_arguments.len(),
+                params.len()
+            )));
+        }
+
+        let (fixed_params, remaining_params) = params.split_at(fixed_arguments.len());
+        let fixed_arguments = self.to_wasm_arguments(py, fixed_arguments, fixed_params)?;
+        let (function, fixed_arguments) = match &self.partial {
+            Some((function, previous_arguments)) => (
+                function.clone(),
+                previous_arguments
+                    .iter()
+                    .cloned()
+                    .chain(fixed_arguments)
+                    .collect::<Vec<_>>(),
+            ),
+            None => (self.inner.clone(), fixed_arguments),
+        };
+
+        // The values are kept as numeric values in the host function,
+        // so that it can be sent between threads. References cannot.
+        let numeric_fixed_arguments = fixed_arguments
             .iter()
-            .zip(self.inner.ty().params().iter().cloned())
-            .map(to_wasm_value)
-            .collect::<PyResult<_>>()?;
+            .enumerate()
+            .map(|(nth, argument)| {
+                NumericValue::new(argument).ok_or_else(|| {
+                    to_py_err::<PyTypeError, _>(format!(
+                        "`Function.partial` cannot bind argument #{} of type `{:?}`, only numbers can be bound",
+                        nth,
+                        argument.ty()
+                    ))
+                })
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let original_function = function.clone();
 
-        let results = self
-            .inner
-            .call(&arguments)
-            .map(<[_]>::into_vec)
-            .map_err(to_py_err::<PyRuntimeError, _>)?;
+        let partial_function = wasmer::Function::new(
+            self.inner.store(),
+            wasmer::FunctionType::new(remaining_params, function_type.results()),
+            move |arguments: &[wasmer::Value]| -> Result<Vec<wasmer::Value>, wasmer::RuntimeError> {
+                let arguments = numeric_fixed_arguments
+                    .iter()
+                    .copied()
+                    .map(wasmer::Value::from)
+                    .chain(arguments.iter().cloned())
+                    .collect::<Vec<_>>();
 
-        let to_py_object = to_py_object(py);
+                original_function.call(&arguments).map(<[_]>::into_vec)
+            },
+        );
 
-        Ok(match results.len() {
-            0 => py.None(),
-            1 => to_py_object(&results[0]),
-            _ => PyTuple::new(
-                py,
-                results.iter().map(to_py_object).collect::<Vec<PyObject>>(),
-            )
-            .to_object(py),
+        Ok(Self {
+            inner: partial_function,
+            name: self.name.clone(),
+            deterministic: self.deterministic,
+            strict_marshaling: self.strict_marshaling,
+            partial: Some((function, fixed_arguments)),
+            stack_usage: self.stack_usage.clone(),
+            interrupts: self.interrupts.clone(),
         })
     }
 
@@ -259,4 +1093,345 @@ impl Function {
     fn ty(&self) -> FunctionType {
         self.inner.ty().into()
     }
+
+    /// Checks whether the function has been flagged as deterministic
+    /// by the user, with `Function(…, deterministic=True)`. Exported
+    /// functions are never flagged as deterministic.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Function
+    ///
+    /// def sum(x: int, y: int) -> int:
+    ///     return x + y
+    ///
+    /// store = Store()
+    ///
+    /// assert Function(store, sum, deterministic=True).deterministic == True
+    /// assert Function(store, sum).deterministic == False
+    /// ```
+    #[getter]
+    fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// The name of the function: the export name for an exported
+    /// function, or the name of the Python callable for a host
+    /// function. It allows generic decorators or loggers to read
+    /// `__name__` as with any Python function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Function
+    ///
+    /// store = Store()
+    /// module = Module(store, '(module (func (export "sum")))')
+    ///
+    /// assert Instance(module).exports.sum.__name__ == 'sum'
+    ///
+    /// def product(x: int, y: int) -> int:
+    ///     return x * y
+    ///
+    /// assert Function(store, product).__name__ == 'product'
+    /// ```
+    #[getter(__name__)]
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Converts an error raised by a host function into a runtime error.
+/// A `HostTrap` is carried as is, so that it is re-raised to the
+/// caller.
+fn to_runtime_error(py: Python, error: PyErr) -> wasmer::RuntimeError {
+    if error.is_instance::<HostTrap>(py) {
+        wasmer::RuntimeError::from_trap(wasmer_vm::Trap::User(Box::new(error)))
+    } else {
+        wasmer::RuntimeError::new(io::Error::from(error).to_string())
+    }
+}
+
+/// Calls a raw host function, see `Function(…, raw=True)`: the
+/// arguments are given as a list of `Value`, and the results can be
+/// `Value` objects.
+fn call_raw_host_function(
+    py: Python,
+    py_function: &PyObject,
+    arguments: &[wasmer::Value],
+    result_types: &[wasmer::Type],
+    strict_marshaling: bool,
+) -> Result<Vec<wasmer::Value>, wasmer::RuntimeError> {
+    // A type error is raised as is by the caller.
+    let type_error = |message: String| {
+        wasmer::RuntimeError::from_trap(wasmer_vm::Trap::User(Box::new(
+            to_py_err::<PyTypeError, _>(message),
+        )))
+    };
+
+    let arguments = arguments
+        .iter()
+        .map(|argument| Py::new(py, Value::raw_new(argument.clone())))
+        .collect::<PyResult<Vec<_>>>()
+        .map_err(|error| to_runtime_error(py, error))?;
+
+    let results = py_function
+        .call1(py, (PyList::new(py, arguments),))
+        .map_err(|error| to_runtime_error(py, error))?;
+    let results = results.as_ref(py);
+
+    let results = if results.is_none() {
+        Vec::new()
+    } else if results.is_instance::<PyList>().unwrap_or(false)
+        || results.is_instance::<PyTuple>().unwrap_or(false)
+    {
+        results
+            .iter()
+            .and_then(|results| results.collect::<PyResult<Vec<_>>>())
+            .map_err(|error| to_runtime_error(py, error))?
+    } else {
+        vec![results]
+    };
+
+    if results.len() != result_types.len() {
+        return Err(type_error(format!(
+            "The raw function must return {} result(s), got {}",
+            result_types.len(),
+            results.len()
+        )));
+    }
+
+    results
+        .into_iter()
+        .zip(result_types.iter().cloned())
+        .enumerate()
+        .map(|(nth, (result, ty))| {
+            if let Ok(value) = result.downcast::<PyCell<Value>>() {
+                let value = value.borrow().inner().clone();
+
+                return if value.ty() == ty {
+                    Ok(value)
+                } else {
+                    Err(type_error(format!(
+                        "Result #{} must be a value of type `{:?}`, got `{:?}`",
+                        nth,
+                        ty,
+                        value.ty()
+                    )))
+                };
+            }
+
+            if strict_marshaling && !is_strictly_of_type(result, ty) {
+                return Err(type_error(format!(
+                    "Result #{} (`{}`) must be of type `{}` for the `{:?}` result, got `{}` (strict marshaling)",
+                    nth,
+                    result,
+                    python_type_name(ty),
+                    ty,
+                    result.get_type().name().unwrap_or("unknown"),
+                )));
+            }
+
+            to_wasm_value((result, ty))
+                .map_err(|error| wasmer::RuntimeError::new(io::Error::from(error).to_string()))
+        })
+        .collect()
+}
+
+/// Infers the parameter and result types of a Python function from
+/// its annotations.
+fn types_from_annotations(
+    py: Python,
+    py_function: &PyAny,
+) -> PyResult<(Vec<wasmer::Type>, Vec<wasmer::Type>)> {
+    let annotations = annotations(py, py_function)?;
+    let mut argument_types = Vec::new();
+    let mut result_types = Vec::new();
+
+    for (annotation_name, annotation_value) in annotations {
+        let ty = match annotation_value.to_string().as_str() {
+            "i32" | "I32" | "<class 'int'>" => wasmer::Type::I32,
+            "i64" | "I64" => wasmer::Type::I64,
+            "f32" | "F32" | "<class 'float'>" => wasmer::Type::F32,
+            "f64" | "F64" => wasmer::Type::F64,
+            ty => {
+                return Err(to_py_err::<PyRuntimeError, _>(format!(
+                    "Type `{}` is not a supported type",
+                    ty,
+                )))
+            }
+        };
+
+        match annotation_name.to_string().as_str() {
+            "return" => result_types.push(ty),
+            _ => argument_types.push(ty),
+        }
+    }
+
+    Ok((argument_types, result_types))
+}
+
+/// Reads the annotations of `py_function`. They are resolved with
+/// `typing.get_type_hints`, so that postponed annotations (see PEP
+/// 563) are supported. Annotations that cannot be resolved, like
+/// `'i32'`, are read as is.
+fn annotations<'p>(py: Python<'p>, py_function: &'p PyAny) -> PyResult<&'p PyDict> {
+    match py
+        .import("typing")?
+        .call_method1("get_type_hints", (py_function,))
+    {
+        Ok(type_hints) => type_hints,
+        Err(_) => py_function.getattr("__annotations__")?,
+    }
+    .downcast::<PyDict>()
+    .map_err(PyErr::from)
+}
+
+/// Returns the WebAssembly types an annotation is compatible with:
+/// `int` is compatible with `I32` and `I64`, `float` with `F32` and
+/// `F64`, and a named type like `'i64'` with this type only. It
+/// returns `None` for an unsupported annotation.
+fn compatible_types(annotation: &PyAny) -> Option<&'static [wasmer::Type]> {
+    Some(match annotation.to_string().as_str() {
+        "<class 'int'>" => &[wasmer::Type::I32, wasmer::Type::I64],
+        "<class 'float'>" => &[wasmer::Type::F32, wasmer::Type::F64],
+        "i32" | "I32" => &[wasmer::Type::I32],
+        "i64" | "I64" => &[wasmer::Type::I64],
+        "f32" | "F32" => &[wasmer::Type::F32],
+        "f64" | "F64" => &[wasmer::Type::F64],
+        _ => return None,
+    })
+}
+
+/// Checks that the annotations of `py_function` agree with
+/// `function_type`, parameter by parameter, by position. The
+/// parameters without annotation, or with an unsupported one, are
+/// not checked.
+fn check_annotations(
+    py: Python,
+    py_function: &PyAny,
+    function_type: &wasmer::FunctionType,
+) -> PyResult<()> {
+    let annotations = match annotations(py, py_function) {
+        Ok(annotations) => annotations,
+        Err(_) => return Ok(()),
+    };
+    let parameters = match py
+        .import("inspect")?
+        .call_method1("signature", (py_function,))
+    {
+        Ok(signature) => signature.getattr("parameters")?.call_method0("values")?,
+        Err(_) => return Ok(()),
+    };
+    let annotation_name = |annotation: &PyAny| {
+        annotation
+            .getattr("__name__")
+            .map(ToString::to_string)
+            .unwrap_or_else(|_| annotation.to_string())
+    };
+
+    let mut nth = 0;
+
+    for parameter in parameters.iter()? {
+        let parameter = parameter?;
+        let kind = parameter.getattr("kind")?.getattr("name")?;
+
+        // `*args`, `**kwargs` and keyword-only parameters are not
+        // given by WebAssembly.
+        if !matches!(
+            kind.extract::<&str>()?,
+            "POSITIONAL_ONLY" | "POSITIONAL_OR_KEYWORD"
+        ) {
+            continue;
+        }
+
+        let name = parameter.getattr("name")?.extract::<&str>()?;
+        let ty = function_type.params().get(nth);
+        nth += 1;
+
+        let annotation = match annotations.get_item(name) {
+            Some(annotation) => annotation,
+            None => continue,
+        };
+        let types = match compatible_types(annotation) {
+            Some(types) => types,
+            None => continue,
+        };
+
+        match ty {
+            Some(ty) if types.contains(ty) => (),
+            Some(ty) => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The annotation `{}` of the parameter `{}` does not match the parameter #{} of type `{:?}` of the function type",
+                    annotation_name(annotation),
+                    name,
+                    nth - 1,
+                    ty,
+                )))
+            }
+            None => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The annotated parameter `{}` has no matching parameter in the function type `{:?}`",
+                    name,
+                    function_type.params(),
+                )))
+            }
+        }
+    }
+
+    if let Some(annotation) = annotations.get_item("return") {
+        if let Some(types) = compatible_types(annotation) {
+            match function_type.results() {
+                [ty] if types.contains(ty) => (),
+                results => {
+                    return Err(to_py_err::<PyValueError, _>(format!(
+                        "The return annotation `{}` does not match the results `{:?}` of the function type",
+                        annotation_name(annotation),
+                        results,
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A numeric WebAssembly value. Contrary to `wasmer::Value`, it can
+/// be sent between threads, since it cannot hold a reference.
+#[derive(Clone, Copy)]
+enum NumericValue {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    V128(u128),
+}
+
+impl NumericValue {
+    /// Converts a WebAssembly value, or returns `None` if it is a
+    /// reference.
+    fn new(value: &wasmer::Value) -> Option<Self> {
+        Some(match *value {
+            wasmer::Value::I32(value) => Self::I32(value),
+            wasmer::Value::I64(value) => Self::I64(value),
+            wasmer::Value::F32(value) => Self::F32(value),
+            wasmer::Value::F64(value) => Self::F64(value),
+            wasmer::Value::V128(value) => Self::V128(value),
+            wasmer::Value::ExternRef(_) | wasmer::Value::FuncRef(_) => return None,
+        })
+    }
+}
+
+impl From<NumericValue> for wasmer::Value {
+    fn from(value: NumericValue) -> Self {
+        match value {
+            NumericValue::I32(value) => Self::I32(value),
+            NumericValue::I64(value) => Self::I64(value),
+            NumericValue::F32(value) => Self::F32(value),
+            NumericValue::F64(value) => Self::F64(value),
+            NumericValue::V128(value) => Self::V128(value),
+        }
+    }
 }
@@ -1,13 +1,15 @@
 use crate::{
     errors::to_py_err,
     store::Store,
-    types::GlobalType,
+    types::{GlobalType, Type},
     values::{to_py_object, to_wasm_value, Value},
     wasmer_inner::wasmer,
 };
 use pyo3::{
-    exceptions::{PyRuntimeError, PyValueError},
+    class::number::PyNumberProtocol,
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
+    types::{PyFloat, PyLong},
 };
 
 /// Represents a WebAssembly global instance.
@@ -38,35 +40,72 @@ use pyo3::{
 ///
 /// assert global_.mutable == True
 /// ```
+///
+/// A global holding a number can be used directly as a number with
+/// `int()` and `float()`, which read its current value. A global
+/// holding a reference raises a `TypeError`.
+///
+/// ```py
+/// from wasmer import Store, Global, Value
+///
+/// store = Store()
+/// global_ = Global(store, Value.i32(42), mutable=True)
+///
+/// assert int(global_) == 42
+/// assert float(global_) == 42.0
+///
+/// global_.value = 153
+///
+/// assert int(global_) + 1 == 154
+/// ```
 #[pyclass(unsendable)]
 #[text_signature = "(store, value, mutable)"]
 pub struct Global {
     inner: wasmer::Global,
+    initial_value: wasmer::Value,
 }
 
 impl Global {
-    pub fn raw_new(inner: wasmer::Global) -> Self {
-        Self { inner }
+    pub fn raw_new(inner: wasmer::Global, initial_value: wasmer::Value) -> Self {
+        Self {
+            inner,
+            initial_value,
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Global {
         &self.inner
     }
+
+    /// Reads the current value of the global as a Python number, or
+    /// raises a `TypeError` if it is a reference.
+    fn number(&self, py: Python) -> PyResult<PyObject> {
+        match self.inner.get() {
+            value @ (wasmer::Value::ExternRef(_) | wasmer::Value::FuncRef(_)) => {
+                Err(to_py_err::<PyTypeError, _>(format!(
+                    "The global holds a value of type `{:?}`, which is not a number",
+                    value.ty()
+                )))
+            }
+            value => Ok(to_py_object(py)(&value)),
+        }
+    }
 }
 
 #[pymethods]
 impl Global {
     #[new]
-    fn new(store: &Store, value: &Value, mutable: Option<bool>) -> Self {
-        let store = store.inner();
+    fn new(store: &Store, value: &Value, mutable: Option<bool>) -> PyResult<Self> {
+        let store = store.inner()?;
         let value = value.inner().clone();
 
-        Self {
+        Ok(Self {
             inner: match mutable {
-                Some(true) => wasmer::Global::new_mut(store, value),
-                _ => wasmer::Global::new(store, value),
+                Some(true) => wasmer::Global::new_mut(store, value.clone()),
+                _ => wasmer::Global::new(store, value.clone()),
             },
-        }
+            initial_value: value,
+        })
     }
 
     /// Checks whether the global is mutable.
@@ -126,23 +165,110 @@ impl Global {
         Ok(())
     }
 
-    /// Returns the type of the global as a value of kind `GlobalType`.
+    /// Returns the type of the global as a `GlobalType` object, i.e.
+    /// the type of its value _and_ its mutability. To get the type of
+    /// the value only, use `Global.value_type`.
     ///
     /// ## Example
     ///
     /// ```py
-    /// from wasmer import Store, Global, Value, Type
+    /// from wasmer import Store, Global, GlobalType, Value, Type
     ///
     /// store = Store()
-    ///
-    /// global_ = Global(store, Value.i32(42), mutable=False)
+    /// global_ = Global(store, Value.i32(42), mutable=True)
     /// global_type = global_.type
     ///
+    /// assert isinstance(global_type, GlobalType)
     /// assert global_type.type == Type.I32
-    /// assert global_type.mutable == False
+    /// assert global_type.mutable == True
     /// ```
     #[getter(type)]
     fn ty(&self) -> GlobalType {
         self.inner.ty().into()
     }
+
+    /// Restores a mutable global to its initial value, i.e. the value
+    /// it had when the instance was created (for an exported global),
+    /// or the value given to the constructor (for a host global). It
+    /// is handy to reuse an instance from a clean state.
+    ///
+    /// An immutable global has nothing to reset, and raises a
+    /// `RuntimeError`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (global $counter (export "counter") (mut i32) (i32.const 7))
+    ///       (func (export "increment")
+    ///         global.get $counter
+    ///         i32.const 1
+    ///         i32.add
+    ///         global.set $counter))
+    ///     """
+    /// )
+    /// exports = Instance(module).exports
+    ///
+    /// exports.increment()
+    /// exports.increment()
+    ///
+    /// assert exports.counter.value == 9
+    ///
+    /// exports.counter.reset()
+    ///
+    /// assert exports.counter.value == 7
+    /// ```
+    #[text_signature = "($self)"]
+    fn reset(&self) -> PyResult<()> {
+        if !self.inner.ty().mutability.is_mutable() {
+            return Err(to_py_err::<PyRuntimeError, _>(
+                "The global variable is not mutable, there is nothing to reset",
+            ));
+        }
+
+        self.inner
+            .set(self.initial_value.clone())
+            .map_err(to_py_err::<PyValueError, _>)
+    }
+
+    /// Returns the `Type` of the value held by the global. It is a
+    /// shortcut for `Global.type.type`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Global, Value, Type
+    ///
+    /// store = Store()
+    /// global_ = Global(store, Value.f64(4.2))
+    ///
+    /// assert global_.value_type == Type.F64
+    /// assert global_.value_type == global_.type.type
+    /// ```
+    #[getter]
+    fn value_type(&self) -> Type {
+        (&self.inner.ty().ty).into()
+    }
+}
+
+#[pyproto]
+impl PyNumberProtocol for Global {
+    fn __int__(&self) -> PyResult<PyObject> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        Ok(py.get_type::<PyLong>().call1((self.number(py)?,))?.into())
+    }
+
+    fn __float__(&self) -> PyResult<PyObject> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        Ok(py.get_type::<PyFloat>().call1((self.number(py)?,))?.into())
+    }
 }
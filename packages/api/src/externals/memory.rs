@@ -1,11 +1,26 @@
 use crate::{
     errors::to_py_err,
-    memory::{Buffer, Int16Array, Int32Array, Int8Array, Uint16Array, Uint32Array, Uint8Array},
+    memory::{
+        exported_buffers, Buffer, Int16Array, Int32Array, Int8Array, Uint16Array, Uint32Array,
+        Uint8Array,
+    },
     store::Store,
-    types::MemoryType,
+    types::{MemoryType, Type},
+    values::{to_py_object, to_wasm_value},
     wasmer_inner::wasmer,
 };
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
+use pyo3::{
+    exceptions::{PyBufferError, PyIndexError, PyRuntimeError, PyTypeError, PyValueError},
+    ffi,
+    once_cell::GILOnceCell,
+    prelude::*,
+    types::{PyBytes, PyString, PyTuple},
+    AsPyPointer,
+};
+use std::{
+    cmp::min,
+    convert::{TryFrom, TryInto},
+};
 
 /// A WebAssembly memory instance.
 ///
@@ -63,6 +78,161 @@ impl Memory {
     pub(crate) fn inner(&self) -> &wasmer::Memory {
         &self.inner
     }
+
+    /// Checks that `length` bytes starting at `offset` fit in the
+    /// memory, and returns the end offset.
+    fn check_bounds(&self, offset: usize, length: usize) -> PyResult<usize> {
+        let data_size = self.inner.data_size() as usize;
+
+        offset
+            .checked_add(length)
+            .filter(|end| *end <= data_size)
+            .ok_or_else(|| {
+                to_py_err::<PyIndexError, _>(format!(
+                    "Out of bound: The slice `{}:{}` is larger than the memory size {}",
+                    offset,
+                    offset.saturating_add(length),
+                    data_size
+                ))
+            })
+    }
+
+    /// Checks that no buffer over the memory is exported, since
+    /// growing the memory may reallocate it and leave the consumers
+    /// of the buffers with dangling pointers.
+    fn check_no_exported_buffers(&self) -> PyResult<()> {
+        match exported_buffers(&self.inner) {
+            0 => Ok(()),
+            exports => Err(to_py_err::<PyBufferError, _>(format!(
+                "The memory cannot grow while {} buffer export(s) are outstanding \
+                 (e.g. a `memoryview` or a NumPy array); release them first",
+                exports
+            ))),
+        }
+    }
+
+    /// Writes `bytes` at `offset`, and returns the number of written
+    /// bytes.
+    pub(crate) fn write_bytes(&self, offset: usize, bytes: &[u8]) -> PyResult<usize> {
+        let end = self.check_bounds(offset, bytes.len())?;
+        let view = self.inner.view::<u8>();
+
+        for (cell, byte) in view[offset..end].iter().zip(bytes.iter()) {
+            cell.set(*byte);
+        }
+
+        Ok(bytes.len())
+    }
+
+    /// Creates a NumPy array of `count` items of the NumPy `dtype`,
+    /// sharing the memory data at `offset`, see
+    /// `Instance.call_returning_array`.
+    pub(crate) fn numpy_array(
+        &self,
+        py: Python,
+        dtype: &PyAny,
+        offset: usize,
+        count: usize,
+    ) -> PyResult<PyObject> {
+        let numpy = py.import("numpy")?;
+        let dtype = numpy.getattr("dtype")?.call1((dtype,))?;
+        let item_size = dtype.getattr("itemsize")?.extract::<usize>()?;
+
+        let length = count.checked_mul(item_size).ok_or_else(|| {
+            to_py_err::<PyIndexError, _>(format!("The count {} is too large", count))
+        })?;
+        self.check_bounds(offset, length)?;
+
+        let buffer = Py::new(py, Buffer::new_slice(self.inner.clone(), offset, length))?;
+
+        Ok(numpy
+            .getattr("frombuffer")?
+            .call1((buffer, dtype))?
+            .to_object(py))
+    }
+
+    /// Checks whether the memory has the same size and bytes as
+    /// `other`, see `memories_equal`.
+    pub(crate) fn has_same_contents(&self, other: &Memory) -> bool {
+        // SAFETY: the data is only read, while holding the GIL, and
+        // no WebAssembly code runs in the meantime in this thread.
+        unsafe { self.inner.data_unchecked() == other.inner.data_unchecked() }
+    }
+
+    /// Reads the `N` bytes at `offset`, e.g. to decode a scalar.
+    fn read_array_of_bytes<const N: usize>(&self, offset: usize) -> PyResult<[u8; N]> {
+        let end = self.check_bounds(offset, N)?;
+        let view = self.inner.view::<u8>();
+        let mut bytes = [0; N];
+
+        for (byte, cell) in bytes.iter_mut().zip(view[offset..end].iter()) {
+            *byte = cell.get();
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// What to do when a host read or write is out of the memory bounds,
+/// see `Memory.read` and `Memory.write`.
+enum OutOfBoundsPolicy<'p> {
+    /// Raise an `IndexError`.
+    Raise,
+    /// Restrict the access to the available bytes.
+    Clamp,
+    /// Call a user callback, whose result is returned instead.
+    Callback(&'p PyAny),
+}
+
+impl<'p> OutOfBoundsPolicy<'p> {
+    fn new(policy: Option<&'p PyAny>) -> PyResult<Self> {
+        let policy = match policy {
+            Some(policy) if !policy.is_none() => policy,
+            _ => return Ok(Self::Raise),
+        };
+
+        if policy.is_callable() {
+            return Ok(Self::Callback(policy));
+        }
+
+        match policy.extract::<&str>() {
+            Ok("raise") => Ok(Self::Raise),
+            Ok("clamp") => Ok(Self::Clamp),
+            _ => Err(to_py_err::<PyValueError, _>(format!(
+                "The out-of-bounds policy must be `\"raise\"`, `\"clamp\"`, or a callable; given `{}`",
+                policy
+            ))),
+        }
+    }
+}
+
+/// Returns the size (in bytes) of a value of a numeric type.
+fn size_of_type(ty: wasmer::Type) -> PyResult<usize> {
+    Type::from(&ty).byte_size().ok_or_else(|| {
+        to_py_err::<PyTypeError, _>(format!("Arrays of `{:?}` values are not supported", ty))
+    })
+}
+
+/// Checks whether `endianness` is `"big"`, or `"little"`.
+fn is_big_endian(endianness: &str) -> PyResult<bool> {
+    match endianness {
+        "little" => Ok(false),
+        "big" => Ok(true),
+        endianness => Err(to_py_err::<PyValueError, _>(format!(
+            "The endianness must be `\"little\"` or `\"big\"`; given `{}`",
+            endianness
+        ))),
+    }
+}
+
+/// Returns the `struct` format `format`, in little-endian unless it
+/// starts with a byte order character.
+fn little_endian_format(format: &str) -> String {
+    if format.starts_with(['@', '=', '<', '>', '!']) {
+        format.to_string()
+    } else {
+        format!("<{}", format)
+    }
 }
 
 #[pymethods]
@@ -70,7 +240,7 @@ impl Memory {
     #[new]
     fn new(store: &Store, memory_type: &MemoryType) -> PyResult<Self> {
         Ok(Self::raw_new(
-            wasmer::Memory::new(store.inner(), memory_type.into())
+            wasmer::Memory::new(store.inner()?, memory_type.into())
                 .map_err(to_py_err::<PyRuntimeError, _>)?,
         ))
     }
@@ -113,6 +283,11 @@ impl Memory {
 
     /// Grow memory by the specified amount of WebAssembly pages.
     ///
+    /// The memory cannot grow beyond the maximum of its memory type,
+    /// in which case a `RuntimeError` is raised. The maximum is also
+    /// enforced when the memory is imported by a module and grown by
+    /// the `memory.grow` instruction, which then returns -1.
+    ///
     /// ## Example
     ///
     /// ```py
@@ -128,12 +303,281 @@ impl Memory {
     ///
     /// assert memory.size == 5
     /// ```
-    #[text_signature = "($self, number_of_pages)"]
-    fn grow(&self, number_of_pages: u32) -> PyResult<u32> {
-        self.inner
+    ///
+    /// Growing the memory may reallocate it elsewhere, which
+    /// invalidates pointers in it (including the buffers and views
+    /// built before). With `report=True`, it returns a
+    /// `MemoryGrowth(previous_pages, previous_data_ptr, data_ptr)`
+    /// named tuple instead of the previous number of pages, so that
+    /// it is possible to detect when cached pointers became stale:
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// growth = memory.grow(1, report=True)
+    ///
+    /// assert growth.previous_pages == 1
+    /// assert isinstance(growth.previous_data_ptr, int)
+    /// assert isinstance(growth.data_ptr, int)
+    ///
+    /// if growth.data_ptr != growth.previous_data_ptr:
+    ///     pass # The memory has moved, pointers must be refreshed.
+    /// ```
+    ///
+    /// Views stay valid, since they fetch the memory data on each
+    /// access. However, as long as a `Buffer` is exported, e.g. to a
+    /// `memoryview` or a NumPy array, the memory cannot grow and a
+    /// `BufferError` is raised; see the `Buffer` class to learn more.
+    #[text_signature = "($self, number_of_pages, report)"]
+    #[args(report = "false")]
+    fn grow(&self, py: Python, number_of_pages: u32, report: bool) -> PyResult<PyObject> {
+        self.check_no_exported_buffers()?;
+
+        let previous_data_ptr = self.inner.data_ptr() as usize;
+        let previous_pages = self
+            .inner
             .grow(number_of_pages)
             .map(|pages| pages.0)
-            .map_err(to_py_err::<PyRuntimeError, _>)
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        if !report {
+            return Ok(previous_pages.into_py(py));
+        }
+
+        static MEMORY_GROWTH: GILOnceCell<PyObject> = GILOnceCell::new();
+
+        let memory_growth = match MEMORY_GROWTH.get(py) {
+            Some(memory_growth) => memory_growth,
+            None => {
+                let memory_growth = py
+                    .import("collections")?
+                    .call_method1(
+                        "namedtuple",
+                        (
+                            "MemoryGrowth",
+                            ["previous_pages", "previous_data_ptr", "data_ptr"],
+                        ),
+                    )?
+                    .to_object(py);
+                let _ = MEMORY_GROWTH.set(py, memory_growth);
+
+                MEMORY_GROWTH.get(py).unwrap()
+            }
+        };
+
+        memory_growth.call1(
+            py,
+            (
+                previous_pages,
+                previous_data_ptr,
+                self.inner.data_ptr() as usize,
+            ),
+        )
+    }
+
+    /// Grows the memory like `Memory.grow`, but returns `None`
+    /// instead of raising when the memory cannot grow, e.g. when it
+    /// would exceed its maximum number of pages, or while a `Buffer`
+    /// is exported. It returns the previous number of pages
+    /// otherwise. It is handy to attempt to grow the memory, and to
+    /// fall back gracefully.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, maximum=2, shared=False))
+    ///
+    /// assert memory.try_grow(1) == 1
+    /// assert memory.try_grow(1) == None
+    /// assert memory.size == 2
+    /// ```
+    #[text_signature = "($self, number_of_pages)"]
+    fn try_grow(&self, number_of_pages: u32) -> Option<u32> {
+        if exported_buffers(&self.inner) > 0 {
+            return None;
+        }
+
+        self.inner.grow(number_of_pages).ok().map(|pages| pages.0)
+    }
+
+    /// Grow memory by as many WebAssembly pages as needed to hold
+    /// `byte_length` bytes. Nothing happens if the memory is already
+    /// large enough.
+    ///
+    /// It returns the new size (in bytes) of the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory_type = MemoryType(1, shared=False)
+    /// memory = Memory(store, memory_type)
+    ///
+    /// assert memory.grow_to_fit(42) == 65536
+    /// assert memory.grow_to_fit(65537) == 131072
+    /// assert memory.size == 2
+    /// ```
+    #[text_signature = "($self, byte_length)"]
+    fn grow_to_fit(&self, byte_length: u64) -> PyResult<u64> {
+        let page_size = wasmer::WASM_PAGE_SIZE as u64;
+        let needed_pages = byte_length.saturating_add(page_size - 1) / page_size;
+        let needed_pages = u32::try_from(needed_pages).map_err(|_| {
+            to_py_err::<PyValueError, _>(format!(
+                "Cannot grow the memory to fit {} bytes; it exceeds the maximum number of pages",
+                byte_length
+            ))
+        })?;
+        let current_pages = self.inner.size().0;
+
+        if needed_pages > current_pages {
+            self.check_no_exported_buffers()?;
+
+            self.inner
+                .grow(needed_pages - current_pages)
+                .map_err(to_py_err::<PyRuntimeError, _>)?;
+        }
+
+        Ok(self.inner.data_size())
+    }
+
+    /// Captures the full memory data, as `bytes`. It is the
+    /// counterpart of `Memory.restore`, to run a module from a clean
+    /// state without instantiating it again.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// snapshot = memory.snapshot()
+    ///
+    /// assert len(snapshot) == memory.data_size
+    /// ```
+    #[text_signature = "($self)"]
+    fn snapshot<'p>(&self, py: Python<'p>) -> &'p PyBytes {
+        let view = self.inner.view::<u8>();
+        let bytes = view.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
+
+        PyBytes::new(py, &bytes)
+    }
+
+    /// Copies the memory data into an immutable `bytes`, for logging
+    /// or diffing for example. Contrary to `Memory.buffer`, the data
+    /// is copied, so the result does not change when the memory is
+    /// modified later.
+    ///
+    /// By default, the full memory is copied, which is expensive for
+    /// large memories. A range can be copied instead, with `offset`
+    /// and `length`; without `length`, the range extends to the end
+    /// of the memory. An `IndexError` is raised if the range does not
+    /// fit in the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write(0, b'Wasmer')
+    ///
+    /// data = memory.to_bytes()
+    ///
+    /// assert isinstance(data, bytes)
+    /// assert len(data) == memory.data_size
+    /// assert memory.to_bytes(0, 6) == b'Wasmer'
+    /// assert memory.to_bytes(65530) == bytes(6)
+    /// ```
+    #[text_signature = "($self, offset, length)"]
+    #[args(offset = "0", length = "None")]
+    fn to_bytes<'p>(
+        &self,
+        py: Python<'p>,
+        offset: usize,
+        length: Option<usize>,
+    ) -> PyResult<&'p PyBytes> {
+        let data_size = self.inner.data_size() as usize;
+        let length = length.unwrap_or_else(|| data_size.saturating_sub(offset));
+        let end = self.check_bounds(offset, length)?;
+        let view = self.inner.view::<u8>();
+        let bytes = view[offset..end]
+            .iter()
+            .map(|cell| cell.get())
+            .collect::<Vec<u8>>();
+
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    /// Writes back a snapshot, as created by `Memory.snapshot`, to
+    /// the memory.
+    ///
+    /// If the memory is smaller than the snapshot, it grows to fit
+    /// it. If the memory is larger than the snapshot (it has grown
+    /// since the snapshot), it cannot shrink, and a `ValueError` is
+    /// raised. A `ValueError` is also raised if the snapshot size is
+    /// not a whole number of pages. In both cases, the memory is left
+    /// untouched.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// uint8 = memory.uint8_view()
+    ///
+    /// uint8[0] = 1
+    /// snapshot = memory.snapshot()
+    /// uint8[0] = 2
+    ///
+    /// memory.restore(snapshot)
+    ///
+    /// assert uint8[0] == 1
+    /// ```
+    #[text_signature = "($self, snapshot)"]
+    fn restore(&self, snapshot: &PyBytes) -> PyResult<()> {
+        let snapshot = snapshot.as_bytes();
+        let snapshot_size = snapshot.len() as u64;
+        let data_size = self.inner.data_size();
+
+        // Validate the snapshot before growing the memory, so that an
+        // invalid snapshot leaves the memory untouched.
+        if !snapshot_size.is_multiple_of(wasmer::WASM_PAGE_SIZE as u64) {
+            return Err(to_py_err::<PyValueError, _>(format!(
+                "The snapshot size ({} bytes) is not a multiple of the page size ({} bytes)",
+                snapshot_size,
+                wasmer::WASM_PAGE_SIZE
+            )));
+        }
+
+        if snapshot_size < data_size {
+            return Err(to_py_err::<PyValueError, _>(format!(
+                "The snapshot size ({} bytes) does not match the memory size ({} bytes)",
+                snapshot_size, data_size
+            )));
+        }
+
+        self.grow_to_fit(snapshot_size)?;
+
+        let view = self.inner.view::<u8>();
+
+        for (cell, byte) in view.iter().zip(snapshot.iter()) {
+            cell.set(*byte);
+        }
+
+        Ok(())
     }
 
     /// Creates a Python buffer to read and write the memory data. See
@@ -155,6 +599,658 @@ impl Memory {
         Buffer::new(self.inner.clone())
     }
 
+    /// Creates a read-only `memoryview` over `length` bytes of the
+    /// memory data, starting at `offset`. Contrary to copying the
+    /// data, e.g. with `bytes(memory.buffer)`, no data is copied: the
+    /// `memoryview` reads the live memory.
+    ///
+    /// Writing through the `memoryview` is not allowed. Also, the
+    /// memory cannot grow as long as the `memoryview` is alive, since
+    /// the memory data may be moved: `grow` raises a `BufferError`,
+    /// and the `memory.grow` instruction returns -1. Release the
+    /// `memoryview` with `memoryview.release()` before growing the
+    /// memory, and create a new one afterwards.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// uint8 = memory.uint8_view()
+    /// uint8[3:9] = b'Wasmer'
+    ///
+    /// slice = memory.slice(3, 6)
+    ///
+    /// assert slice.readonly
+    /// assert slice.tobytes() == b'Wasmer'
+    ///
+    /// uint8[3] = ord('w')
+    ///
+    /// assert slice.tobytes() == b'wasmer'
+    ///
+    /// slice.release()
+    /// memory.grow(1)
+    ///
+    /// assert memory.slice(3, 6).tobytes() == b'wasmer'
+    /// ```
+    #[text_signature = "($self, offset, length)"]
+    fn slice(&self, py: Python, offset: usize, length: usize) -> PyResult<PyObject> {
+        self.check_bounds(offset, length)?;
+
+        let buffer = Py::new(
+            py,
+            Buffer::new_readonly_slice(self.inner.clone(), offset, length),
+        )?;
+
+        unsafe {
+            PyObject::from_owned_ptr_or_err(py, ffi::PyMemoryView_FromObject(buffer.as_ptr()))
+        }
+    }
+
+    /// Creates a typed view over `count` items of the memory data,
+    /// starting at `offset`, for the given `array` module typecode
+    /// (e.g. `'i'` or `'d'`). It is handy to read or write typed
+    /// numbers with the standard library only, without NumPy.
+    ///
+    /// An `array.array` always owns its data, so it cannot share the
+    /// memory data. The view is thus a `memoryview` cast to the
+    /// typecode, which is indexed, sliced, iterated, and converted
+    /// with `tolist` like an `array.array`. It shares the memory data,
+    /// so writes are reflected in both ways. A typecode that
+    /// `memoryview` cannot represent (e.g. `'u'`) gives an
+    /// `array.array` copy instead, and a `RuntimeWarning` is emitted.
+    ///
+    /// Without `count`, the view extends to the end of the memory. An
+    /// `IndexError` is raised if the items do not fit in the memory.
+    /// Like any buffer export, the view prevents the memory from
+    /// growing until it is released.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// view = memory.as_array('i', offset=8, count=4)
+    /// view[1] = 42
+    ///
+    /// assert len(view) == 4
+    /// assert view.tolist() == [0, 42, 0, 0]
+    /// assert memory.read_i32(12) == 42
+    ///
+    /// view.release()
+    /// ```
+    #[text_signature = "($self, typecode, offset, count)"]
+    #[args(offset = "0", count = "None")]
+    fn as_array(
+        &self,
+        py: Python,
+        typecode: &str,
+        offset: usize,
+        count: Option<usize>,
+    ) -> PyResult<PyObject> {
+        // Let the `array` module validate the typecode.
+        let array = py.import("array")?.getattr("array")?;
+        let item_size = array
+            .call1((typecode,))?
+            .getattr("itemsize")?
+            .extract::<usize>()?;
+
+        let count = count.unwrap_or_else(|| {
+            (self.inner.data_size() as usize).saturating_sub(offset) / item_size
+        });
+        let length = count.checked_mul(item_size).ok_or_else(|| {
+            to_py_err::<PyIndexError, _>(format!("The count {} is too large", count))
+        })?;
+        let end = self.check_bounds(offset, length)?;
+
+        if "bBhHiIlLqQfd".contains(typecode) {
+            let buffer = Py::new(py, Buffer::new_slice(self.inner.clone(), offset, length))?;
+            let memory_view = unsafe {
+                PyObject::from_owned_ptr_or_err(py, ffi::PyMemoryView_FromObject(buffer.as_ptr()))?
+            };
+
+            return memory_view.call_method1(py, "cast", (typecode,));
+        }
+
+        py.import("warnings")?.call_method1(
+            "warn",
+            (
+                format!(
+                    "The `{}` typecode cannot share the memory data, the data is copied",
+                    typecode
+                ),
+                py.import("builtins")?.getattr("RuntimeWarning")?,
+            ),
+        )?;
+
+        let view = self.inner.view::<u8>();
+        let bytes = view[offset..end]
+            .iter()
+            .map(|cell| cell.get())
+            .collect::<Vec<u8>>();
+
+        Ok(array
+            .call1((typecode, PyBytes::new(py, &bytes)))?
+            .to_object(py))
+    }
+
+    /// Writes a sequence of numbers at `offset`, as a packed
+    /// little-endian array of the given numeric `Type`, and returns
+    /// the number of written bytes. It is the common “pass an array
+    /// by pointer” pattern, done in one operation. Floats are
+    /// written in the IEEE 754 format, on 4 bytes for `Type.F32` and
+    /// on 8 bytes for `Type.F64`.
+    ///
+    /// The numbers are written in little-endian, like WebAssembly
+    /// does. With `endianness="big"`, they are written in big-endian
+    /// instead, e.g. for data in the network byte order.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType, Type
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// assert memory.write_array(8, [1, 2, 3], Type.I32) == 12
+    /// assert memory.uint8_view(8)[0:12] == [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]
+    ///
+    /// assert memory.write_array(8, [1, 2], Type.I32, endianness='big') == 8
+    /// assert memory.uint8_view(8)[0:8] == [0, 0, 0, 1, 0, 0, 0, 2]
+    /// ```
+    #[text_signature = "($self, offset, values, type, endianness)"]
+    #[args(endianness = "\"little\"")]
+    fn write_array(
+        &self,
+        offset: usize,
+        values: &PyAny,
+        r#type: Type,
+        endianness: &str,
+    ) -> PyResult<usize> {
+        let ty: wasmer::Type = r#type.into();
+        let big_endian = is_big_endian(endianness)?;
+
+        // Only numeric types are supported.
+        size_of_type(ty)?;
+
+        let mut bytes = Vec::new();
+
+        for value in values.iter()? {
+            let start = bytes.len();
+
+            match to_wasm_value((value?, ty))? {
+                wasmer::Value::I32(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+                wasmer::Value::I64(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+                wasmer::Value::F32(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+                wasmer::Value::F64(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+                wasmer::Value::V128(value) => bytes.extend_from_slice(&value.to_le_bytes()),
+                _ => unreachable!(),
+            }
+
+            if big_endian {
+                bytes[start..].reverse();
+            }
+        }
+
+        self.write_bytes(offset, &bytes)
+    }
+
+    /// Reads `count` numbers at `offset`, from a packed little-endian
+    /// array of the given numeric `Type`. It is the counterpart of
+    /// `Memory.write_array`, including for the `endianness`
+    /// (`"little"` by default, or `"big"`).
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType, Type
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write_array(8, [1.5, 2.5], Type.F64)
+    ///
+    /// assert memory.read_array(8, 2, Type.F64) == [1.5, 2.5]
+    ///
+    /// memory.write(16, b'\x00\x00\x01\x00')
+    ///
+    /// assert memory.read_array(16, 1, Type.I32, endianness='big') == [256]
+    /// ```
+    #[text_signature = "($self, offset, count, type, endianness)"]
+    #[args(endianness = "\"little\"")]
+    fn read_array(
+        &self,
+        py: Python,
+        offset: usize,
+        count: usize,
+        r#type: Type,
+        endianness: &str,
+    ) -> PyResult<Vec<PyObject>> {
+        let ty: wasmer::Type = r#type.into();
+        let big_endian = is_big_endian(endianness)?;
+        let size = size_of_type(ty)?;
+        let length = count.checked_mul(size).ok_or_else(|| {
+            to_py_err::<PyIndexError, _>(format!(
+                "Out of bound: Cannot read {} values of type `{:?}`",
+                count, ty
+            ))
+        })?;
+        let end = self.check_bounds(offset, length)?;
+        let view = self.inner.view::<u8>();
+        let bytes = view[offset..end]
+            .iter()
+            .map(|cell| cell.get())
+            .collect::<Vec<u8>>();
+        let to_py_object = to_py_object(py);
+
+        Ok(bytes
+            .chunks_exact(size)
+            .map(|chunk| {
+                let mut chunk = chunk.to_vec();
+
+                if big_endian {
+                    chunk.reverse();
+                }
+
+                let chunk = chunk.as_slice();
+                let value = match ty {
+                    wasmer::Type::I32 => {
+                        wasmer::Value::I32(i32::from_le_bytes(chunk.try_into().unwrap()))
+                    }
+                    wasmer::Type::I64 => {
+                        wasmer::Value::I64(i64::from_le_bytes(chunk.try_into().unwrap()))
+                    }
+                    wasmer::Type::F32 => {
+                        wasmer::Value::F32(f32::from_le_bytes(chunk.try_into().unwrap()))
+                    }
+                    wasmer::Type::F64 => {
+                        wasmer::Value::F64(f64::from_le_bytes(chunk.try_into().unwrap()))
+                    }
+                    wasmer::Type::V128 => {
+                        wasmer::Value::V128(u128::from_le_bytes(chunk.try_into().unwrap()))
+                    }
+                    _ => unreachable!(),
+                };
+
+                to_py_object(&value)
+            })
+            .collect())
+    }
+
+    /// Reads a C-like structure at `offset`, and returns its fields
+    /// as a tuple, like `struct.unpack` does with the format string
+    /// `format`. The fields are read in little-endian, like
+    /// WebAssembly does, unless `format` starts with another byte
+    /// order character, e.g. `>` for big-endian.
+    ///
+    /// An invalid format raises a `struct.error`, and an `IndexError`
+    /// is raised if the structure does not fit in the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write(8, b'\x2a\x00\x00\x00\x01\x00')
+    ///
+    /// assert memory.unpack('IH', 8) == (42, 1)
+    /// ```
+    #[text_signature = "($self, format, offset)"]
+    fn unpack(&self, py: Python, format: &str, offset: usize) -> PyResult<PyObject> {
+        let format = little_endian_format(format);
+        let r#struct = py.import("struct")?;
+        let size = r#struct
+            .call_method1("calcsize", (format.as_str(),))?
+            .extract::<usize>()?;
+        let end = self.check_bounds(offset, size)?;
+        let view = self.inner.view::<u8>();
+        let bytes = view[offset..end]
+            .iter()
+            .map(|cell| cell.get())
+            .collect::<Vec<u8>>();
+
+        Ok(r#struct
+            .call_method1("unpack", (format.as_str(), PyBytes::new(py, &bytes)))?
+            .to_object(py))
+    }
+
+    /// Writes a C-like structure at `offset`, made of `values`, like
+    /// `struct.pack` does with the format string `format`, and
+    /// returns the number of written bytes. It is the counterpart of
+    /// `Memory.unpack`, including for the byte order.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// assert memory.pack('Id', 8, 42, 1.5) == 12
+    /// assert memory.unpack('Id', 8) == (42, 1.5)
+    /// ```
+    #[text_signature = "($self, format, offset, *values)"]
+    #[args(values = "*")]
+    fn pack(&self, py: Python, format: &str, offset: usize, values: &PyTuple) -> PyResult<usize> {
+        let format = little_endian_format(format);
+        let mut arguments = vec![format.to_object(py)];
+        arguments.extend(values.iter().map(|value| value.to_object(py)));
+
+        let bytes = py
+            .import("struct")?
+            .call_method1("pack", PyTuple::new(py, arguments))?
+            .downcast::<PyBytes>()?
+            .as_bytes();
+
+        self.write_bytes(offset, bytes)
+    }
+
+    /// Reads the 4 bytes at `offset` as a little-endian signed
+    /// integer, i.e. the value is sign-extended: `0xffffffff` is read
+    /// as `-1`. Use `Memory.read_u32` to read the same bytes as an
+    /// unsigned integer.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write(0, b'\xff\xff\xff\xff')
+    ///
+    /// assert memory.read_i32(0) == -1
+    /// assert memory.read_u32(0) == 4294967295
+    /// ```
+    #[text_signature = "($self, offset)"]
+    fn read_i32(&self, offset: usize) -> PyResult<i32> {
+        Ok(i32::from_le_bytes(self.read_array_of_bytes(offset)?))
+    }
+
+    /// Reads the 4 bytes at `offset` as a little-endian unsigned
+    /// integer. See `Memory.read_i32`.
+    #[text_signature = "($self, offset)"]
+    fn read_u32(&self, offset: usize) -> PyResult<u32> {
+        Ok(u32::from_le_bytes(self.read_array_of_bytes(offset)?))
+    }
+
+    /// Reads the 8 bytes at `offset` as a little-endian signed
+    /// integer, i.e. the value is sign-extended. Use
+    /// `Memory.read_u64` to read the same bytes as an unsigned
+    /// integer.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write(0, b'\xff' * 8)
+    ///
+    /// assert memory.read_i64(0) == -1
+    /// assert memory.read_u64(0) == 18446744073709551615
+    /// ```
+    #[text_signature = "($self, offset)"]
+    fn read_i64(&self, offset: usize) -> PyResult<i64> {
+        Ok(i64::from_le_bytes(self.read_array_of_bytes(offset)?))
+    }
+
+    /// Reads the 8 bytes at `offset` as a little-endian unsigned
+    /// integer. See `Memory.read_i64`.
+    #[text_signature = "($self, offset)"]
+    fn read_u64(&self, offset: usize) -> PyResult<u64> {
+        Ok(u64::from_le_bytes(self.read_array_of_bytes(offset)?))
+    }
+
+    /// Writes a string at `offset`, encoded with `encoding` (UTF-8 by
+    /// default), and returns the number of written bytes. No NUL
+    /// terminator is written. Any encoding supported by `str.encode`
+    /// can be used, e.g. `latin-1` or `utf-16-le`, and an encoding
+    /// error raises a `UnicodeEncodeError`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// assert memory.write_string(0, 'café') == 5
+    /// assert bytearray(memory.buffer)[0:5] == b'caf\xc3\xa9'
+    ///
+    /// assert memory.write_string(0, 'café', encoding='latin-1') == 4
+    /// assert bytearray(memory.buffer)[0:4] == b'caf\xe9'
+    /// ```
+    #[text_signature = "($self, offset, string, encoding)"]
+    #[args(encoding = "\"utf-8\"")]
+    fn write_string(&self, offset: usize, string: &PyString, encoding: &str) -> PyResult<usize> {
+        let bytes = string.call_method1("encode", (encoding,))?;
+        let bytes = bytes.downcast::<PyBytes>().map_err(PyErr::from)?;
+
+        self.write_bytes(offset, bytes.as_bytes())
+    }
+
+    /// Reads `length` bytes at `offset`, and returns them as `bytes`.
+    ///
+    /// The `out_of_bounds` policy decides what happens if the bytes
+    /// are not all in the memory:
+    ///
+    /// * `"raise"` (the default) raises an `IndexError`,
+    /// * `"clamp"` reads the available bytes only, possibly none,
+    /// * a callable is called with the `offset`, the `length` and the
+    ///   memory size (in bytes), and its result is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write(0, b'Wasmer')
+    ///
+    /// assert memory.read(0, 6) == b'Wasmer'
+    ///
+    /// # Read at the end of the memory.
+    /// memory.write(65533, b'abc')
+    ///
+    /// assert memory.read(65533, 10, out_of_bounds='clamp') == b'abc'
+    /// assert memory.read(65533, 10, out_of_bounds=lambda offset, length, size: b'') == b''
+    ///
+    /// try:
+    ///     memory.read(65533, 10)
+    /// except IndexError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    #[text_signature = "($self, offset, length, out_of_bounds)"]
+    #[args(out_of_bounds = "None")]
+    fn read(
+        &self,
+        py: Python,
+        offset: usize,
+        length: usize,
+        out_of_bounds: Option<&PyAny>,
+    ) -> PyResult<PyObject> {
+        let policy = OutOfBoundsPolicy::new(out_of_bounds)?;
+        let data_size = self.inner.data_size() as usize;
+        let (start, end) = match (self.check_bounds(offset, length), policy) {
+            (Ok(end), _) => (offset, end),
+            (Err(error), OutOfBoundsPolicy::Raise) => return Err(error),
+            (Err(_), OutOfBoundsPolicy::Clamp) => (min(offset, data_size), data_size),
+            (Err(_), OutOfBoundsPolicy::Callback(callback)) => {
+                return Ok(callback.call1((offset, length, data_size))?.to_object(py))
+            }
+        };
+        let view = self.inner.view::<u8>();
+        let bytes = view[start..end]
+            .iter()
+            .map(|cell| cell.get())
+            .collect::<Vec<u8>>();
+
+        Ok(PyBytes::new(py, &bytes).to_object(py))
+    }
+
+    /// Writes `data` at `offset`, and returns the number of written
+    /// bytes.
+    ///
+    /// The `out_of_bounds` policy decides what happens if the bytes
+    /// do not all fit in the memory:
+    ///
+    /// * `"raise"` (the default) raises an `IndexError`, and nothing
+    ///   is written,
+    /// * `"clamp"` writes the bytes that fit only, possibly none,
+    /// * a callable is called with the `offset`, the length of the
+    ///   data and the memory size (in bytes), nothing is written, and
+    ///   its result is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// assert memory.write(0, b'Wasmer') == 6
+    /// assert memory.write(65533, b'Wasmer', out_of_bounds='clamp') == 3
+    /// assert memory.read(65533, 3) == b'Was'
+    /// ```
+    #[text_signature = "($self, offset, data, out_of_bounds)"]
+    #[args(out_of_bounds = "None")]
+    fn write(
+        &self,
+        py: Python,
+        offset: usize,
+        data: &PyBytes,
+        out_of_bounds: Option<&PyAny>,
+    ) -> PyResult<PyObject> {
+        let policy = OutOfBoundsPolicy::new(out_of_bounds)?;
+        let data = data.as_bytes();
+        let data_size = self.inner.data_size() as usize;
+        let data = match (self.check_bounds(offset, data.len()), policy) {
+            (Ok(_), _) => data,
+            (Err(error), OutOfBoundsPolicy::Raise) => return Err(error),
+            (Err(_), OutOfBoundsPolicy::Clamp) => {
+                &data[..data_size.saturating_sub(offset).min(data.len())]
+            }
+            (Err(_), OutOfBoundsPolicy::Callback(callback)) => {
+                return Ok(callback
+                    .call1((offset, data.len(), data_size))?
+                    .to_object(py))
+            }
+        };
+
+        if data.is_empty() {
+            return Ok(0.into_py(py));
+        }
+
+        Ok(self.write_bytes(offset, data)?.into_py(py))
+    }
+
+    /// Checks whether the `len(data)` bytes at `offset` are equal to
+    /// `data`, without copying the memory. It raises an `IndexError`
+    /// if the bytes are out of the memory bounds.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write_string(4, 'Wasmer')
+    ///
+    /// assert memory.compare(4, b'Wasmer')
+    /// assert not memory.compare(4, b'wasmer')
+    /// ```
+    #[text_signature = "($self, offset, data)"]
+    fn compare(&self, offset: usize, data: &PyBytes) -> PyResult<bool> {
+        let data = data.as_bytes();
+        let end = self.check_bounds(offset, data.len())?;
+        let view = self.inner.view::<u8>();
+
+        Ok(view[offset..end]
+            .iter()
+            .zip(data.iter())
+            .all(|(cell, byte)| cell.get() == *byte))
+    }
+
+    /// Reads a NUL-terminated string (a C `char*`) at `offset`, and
+    /// decodes it as UTF-8. The NUL byte is not part of the string.
+    ///
+    /// At most `max_len` bytes (NUL byte excluded) are read if given,
+    /// otherwise the string can span up to the end of the memory. A
+    /// `ValueError` is raised if no NUL byte is found in between.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    /// memory.write_string(0, 'Hello, World!\0')
+    ///
+    /// assert memory.read_c_string(0) == 'Hello, World!'
+    /// assert memory.read_c_string(7) == 'World!'
+    ///
+    /// try:
+    ///     memory.read_c_string(0, max_len=5)
+    /// except ValueError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    #[text_signature = "($self, offset, max_len)"]
+    #[args(max_len = "None")]
+    fn read_c_string<'p>(
+        &self,
+        py: Python<'p>,
+        offset: usize,
+        max_len: Option<usize>,
+    ) -> PyResult<&'p PyAny> {
+        let data_size = self.inner.data_size() as usize;
+        self.check_bounds(offset, 0)?;
+
+        let end = match max_len {
+            // Include the NUL byte.
+            Some(max_len) => offset
+                .saturating_add(max_len)
+                .saturating_add(1)
+                .min(data_size),
+            None => data_size,
+        };
+        let view = self.inner.view::<u8>();
+        let length = view[offset..end]
+            .iter()
+            .position(|cell| cell.get() == 0)
+            .ok_or_else(|| {
+                to_py_err::<PyValueError, _>(format!(
+                    "No NUL terminator found in the string at offset {} (read up to {})",
+                    offset, end
+                ))
+            })?;
+        let bytes = view[offset..offset + length]
+            .iter()
+            .map(|cell| cell.get())
+            .collect::<Vec<u8>>();
+
+        PyBytes::new(py, &bytes).call_method1("decode", ("utf-8",))
+    }
+
     /// Creates a read-and-write view over the memory data where
     /// elements are of kind `uint8`. See the `Uint8Array` view to
     /// learn more.
@@ -170,72 +1266,90 @@ impl Memory {
     ///
     /// assert isinstance(memory.uint8_view(offset=42), Uint8Array)
     /// ```
-    #[text_signature = "($self, /, offset=0)"]
-    #[args(offset = 0)]
-    fn uint8_view(&self, offset: usize) -> Uint8Array {
+    ///
+    /// The optional `limit` bounds the view to at most `limit`
+    /// elements from `offset`:
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// assert len(memory.uint8_view(offset=42, limit=8)) == 8
+    /// ```
+    #[text_signature = "($self, /, offset=0, limit=None)"]
+    #[args(offset = 0, limit = "None")]
+    fn uint8_view(&self, offset: usize, limit: Option<usize>) -> Uint8Array {
         Uint8Array {
             memory: self.inner.clone(),
             offset,
+            limit,
         }
     }
 
     /// Creates a read-and-write over the memory data where elements
     /// are of kind `int8`. See the `Int8Array` view to learn more,
     /// and the `Memory.uint8_view` method to see an example.
-    #[text_signature = "($self, /, offset=0)"]
-    #[args(offset = 0)]
-    fn int8_view(&self, offset: usize) -> Int8Array {
+    #[text_signature = "($self, /, offset=0, limit=None)"]
+    #[args(offset = 0, limit = "None")]
+    fn int8_view(&self, offset: usize, limit: Option<usize>) -> Int8Array {
         Int8Array {
             memory: self.inner.clone(),
             offset,
+            limit,
         }
     }
 
     /// Creates a read-and-write over the memory data where elements
     /// are of kind `uint16`. See the `Uint16Array` view to learn
     /// more, and the `Memory.uint8_view` method to see an example.
-    #[text_signature = "($self, /, offset=0)"]
-    #[args(offset = 0)]
-    fn uint16_view(&self, offset: usize) -> Uint16Array {
+    #[text_signature = "($self, /, offset=0, limit=None)"]
+    #[args(offset = 0, limit = "None")]
+    fn uint16_view(&self, offset: usize, limit: Option<usize>) -> Uint16Array {
         Uint16Array {
             memory: self.inner.clone(),
             offset,
+            limit,
         }
     }
 
     /// Creates a read-and-write over the memory data where elements
     /// are of kind `int16`. See the `Int16Array` view to learn more,
     /// and the `Memory.uint8_view` method to see an example.
-    #[text_signature = "($self, /, offset=0)"]
-    #[args(offset = 0)]
-    fn int16_view(&self, offset: usize) -> Int16Array {
+    #[text_signature = "($self, /, offset=0, limit=None)"]
+    #[args(offset = 0, limit = "None")]
+    fn int16_view(&self, offset: usize, limit: Option<usize>) -> Int16Array {
         Int16Array {
             memory: self.inner.clone(),
             offset,
+            limit,
         }
     }
 
     /// Creates a read-and-write over the memory data where elements
     /// are of kind `uint32`. See the `Uint32Array` view to learn
     /// more, and the `Memory.uint8_view` method to see an example.
-    #[text_signature = "($self, /, offset=0)"]
-    #[args(offset = 0)]
-    fn uint32_view(&self, offset: usize) -> Uint32Array {
+    #[text_signature = "($self, /, offset=0, limit=None)"]
+    #[args(offset = 0, limit = "None")]
+    fn uint32_view(&self, offset: usize, limit: Option<usize>) -> Uint32Array {
         Uint32Array {
             memory: self.inner.clone(),
             offset,
+            limit,
         }
     }
 
     /// Creates a read-and-write over the memory data where elements
     /// are of kind `int32`. See the `Int32Array` view to learn more,
     /// and the `Memory.uint8_view` method to see an example.
-    #[text_signature = "($self, /, offset=0)"]
-    #[args(offset = 0)]
-    fn int32_view(&self, offset: usize) -> Int32Array {
+    #[text_signature = "($self, /, offset=0, limit=None)"]
+    #[args(offset = 0, limit = "None")]
+    fn int32_view(&self, offset: usize, limit: Option<usize>) -> Int32Array {
         Int32Array {
             memory: self.inner.clone(),
             offset,
+            limit,
         }
     }
 
@@ -1,7 +1,12 @@
 use crate::{
-    errors::to_py_err, store::Store, types::TableType, values::Value, wasmer_inner::wasmer,
+    errors::to_py_err, externals::Function, store::Store, types::TableType, values::Value,
+    wasmer_inner::wasmer,
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+    types::PyList,
 };
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
 
 /// A WebAssembly table instance.
 ///
@@ -11,7 +16,62 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*};
 /// A table created by the host or in WebAssembly code will be
 /// accessible and mutable from both host and WebAssembly.
 ///
+/// The elements of a table created by the host are initialized with
+/// `initial_value`, which is either a `Function` for all of them,
+/// `None` (or `Value.null()`) for null references, or a list with one
+/// `Function` or `None` per element. The list must have as many
+/// items as the minimum size of the table, otherwise a `ValueError`
+/// is raised. It lets the host build a dispatch table to import.
+///
+/// Note that only the functions exported by an instance can be
+/// called through a table: Wasmer cannot call a host function
+/// indirectly yet.
+///
 /// Specification: https://webassembly.github.io/spec/core/exec/runtime.html#table-instances
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, ImportObject, Table, TableType, Type
+///
+/// store = Store()
+/// functions = Instance(
+///     Module(
+///         store,
+///         """
+///         (module
+///           (func (export "one") (result i32) i32.const 1)
+///           (func (export "two") (result i32) i32.const 2))
+///         """
+///     )
+/// ).exports
+///
+/// table = Table(
+///     store,
+///     TableType(Type.FUNC_REF, 3, 3),
+///     [functions.one, None, functions.two]
+/// )
+///
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (type $t (func (result i32)))
+///       (import "env" "table" (table 3 funcref))
+///       (func (export "call") (param i32) (result i32)
+///         local.get 0
+///         call_indirect (type $t)))
+///     """
+/// )
+///
+/// import_object = ImportObject()
+/// import_object.register("env", {"table": table})
+///
+/// instance = Instance(module, import_object)
+///
+/// assert instance.exports.call(0) == 1
+/// assert instance.exports.call(2) == 2
+/// ```
 #[pyclass(unsendable)]
 #[text_signature = "(store, table_type, initial_value)"]
 pub struct Table {
@@ -31,15 +91,42 @@ impl Table {
 #[pymethods]
 impl Table {
     #[new]
-    fn new(store: &Store, table_type: &TableType, initial_value: &Value) -> PyResult<Self> {
-        Ok(Self {
-            inner: wasmer::Table::new(
-                store.inner(),
-                table_type.into(),
-                initial_value.inner().clone(),
-            )
-            .map_err(to_py_err::<PyRuntimeError, _>)?,
-        })
+    #[args(initial_value = "None")]
+    fn new(store: &Store, table_type: &TableType, initial_value: Option<&PyAny>) -> PyResult<Self> {
+        let table_type: wasmer::TableType = table_type.into();
+        let (initial_value, elements) = match initial_value {
+            Some(initial_value) => match initial_value.downcast::<PyList>() {
+                Ok(elements) => {
+                    if elements.len() != table_type.minimum as usize {
+                        return Err(to_py_err::<PyValueError, _>(format!(
+                            "The table has {} initial element(s), but {} element(s) are given",
+                            table_type.minimum,
+                            elements.len()
+                        )));
+                    }
+
+                    let elements = elements
+                        .iter()
+                        .map(|element| to_element(table_type.ty, element))
+                        .collect::<PyResult<Vec<_>>>()?;
+
+                    (wasmer::Value::null(), Some(elements))
+                }
+                Err(_) => (to_element(table_type.ty, initial_value)?, None),
+            },
+            None => (wasmer::Value::null(), None),
+        };
+
+        let inner = wasmer::Table::new(store.inner()?, table_type, initial_value)
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        for (index, element) in elements.into_iter().flatten().enumerate() {
+            inner
+                .set(index as u32, element)
+                .map_err(to_py_err::<PyRuntimeError, _>)?;
+        }
+
+        Ok(Self { inner })
     }
 
     /// Gets the table size (in elements).
@@ -60,6 +147,51 @@ impl Table {
         self.inner.size()
     }
 
+    /// Grows the table by `delta` elements, and returns the previous
+    /// size (in elements) of the table.
+    ///
+    /// The new elements are initialized with `init_func`, which is
+    /// either a `Function`, or `None` to get null references. A
+    /// `Function` can only initialize a table of `Type.FUNC_REF`
+    /// elements.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Table
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (type $answer_t (func (result i32)))
+    ///       (table (export "table") 0 funcref)
+    ///       (func (export "answer") (type $answer_t)
+    ///         i32.const 42)
+    ///       (func (export "call") (param i32) (result i32)
+    ///         local.get 0
+    ///         call_indirect (type $answer_t)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// table = instance.exports.table
+    ///
+    /// assert table.grow(2, instance.exports.answer) == 0
+    /// assert table.size == 2
+    /// assert instance.exports.call(1) == 42
+    /// ```
+    #[text_signature = "($self, delta, init_func)"]
+    fn grow(&self, delta: u32, init_func: Option<PyRef<Function>>) -> PyResult<u32> {
+        let init_value = match init_func {
+            Some(function) => to_function_element(self.inner.ty().ty, &function)?,
+            None => wasmer::Value::ExternRef(wasmer::ExternRef::Null),
+        };
+
+        self.inner
+            .grow(delta, init_value)
+            .map_err(to_py_err::<PyRuntimeError, _>)
+    }
+
     /// Gets the table type, as an object of kind `TableType`.
     ///
     /// ## Example
@@ -81,3 +213,32 @@ impl Table {
         self.inner.ty().into()
     }
 }
+
+/// Converts a `Function` to an element of a table of type `ty`.
+fn to_function_element(ty: wasmer::Type, function: &Function) -> PyResult<wasmer::Value> {
+    if ty != wasmer::Type::FuncRef {
+        return Err(to_py_err::<PyValueError, _>(format!(
+            "A `Function` cannot initialize the elements of a table of type `{:?}`",
+            ty
+        )));
+    }
+
+    Ok(wasmer::Value::FuncRef(function.inner().clone()))
+}
+
+/// Converts a `Function`, a `Value` or `None` to an element of a
+/// table of type `ty`.
+fn to_element(ty: wasmer::Type, element: &PyAny) -> PyResult<wasmer::Value> {
+    if element.is_none() {
+        Ok(wasmer::Value::null())
+    } else if let Ok(function) = element.extract::<PyRef<Function>>() {
+        to_function_element(ty, &function)
+    } else if let Ok(value) = element.extract::<PyRef<Value>>() {
+        Ok(value.inner().clone())
+    } else {
+        Err(to_py_err::<PyTypeError, _>(format!(
+            "A table element must be a `Function`, a `Value` or `None`, given `{}`",
+            element.get_type().name()?
+        )))
+    }
+}
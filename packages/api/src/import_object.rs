@@ -1,13 +1,16 @@
 use crate::{
     errors::to_py_err,
     externals::{Function, Global, Memory, Table},
+    store::Store,
+    types::FunctionType,
     wasmer_inner::wasmer,
 };
 use pyo3::{
-    exceptions::PyTypeError,
+    exceptions::{PyException, PyTypeError},
     prelude::*,
-    types::{PyDict, PyString},
+    types::{PyDict, PyString, PyTuple},
 };
+use std::collections::HashMap;
 
 /// An `ImportObject` represents all of the import data used when
 /// instantiating a WebAssembly module.
@@ -131,18 +134,119 @@ use pyo3::{
 /// etc.
 #[pyclass(unsendable)]
 #[text_signature = "()"]
+#[derive(Clone)]
 pub struct ImportObject {
     inner: wasmer::ImportObject,
+    /// Imports, as `(namespace_name, name)`, deliberately registered
+    /// as `None`.
+    unresolved_imports: Vec<(String, String)>,
+    /// The callable resolving the missing function imports, see
+    /// `ImportObject.set_fallback`.
+    fallback: Option<PyObject>,
 }
 
 impl ImportObject {
     pub(crate) fn raw_new(inner: wasmer::ImportObject) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            unresolved_imports: Vec::new(),
+            fallback: None,
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::ImportObject {
         &self.inner
     }
+
+    pub(crate) fn unresolved_imports(&self) -> &[(String, String)] {
+        &self.unresolved_imports
+    }
+
+    /// Calls the fallback, if any, for each function import of
+    /// `module` that is not registered, see
+    /// `ImportObject.set_fallback`. It returns the resolved imports,
+    /// and the description of the declined ones.
+    pub(crate) fn resolve_with_fallback(
+        &self,
+        py: Python,
+        module: &wasmer::Module,
+    ) -> PyResult<(wasmer::ImportObject, Vec<String>)> {
+        let mut resolved_imports = wasmer::ImportObject::new();
+        let mut declined_imports = Vec::new();
+
+        let fallback = match &self.fallback {
+            Some(fallback) => fallback.as_ref(py),
+            None => return Ok((resolved_imports, declined_imports)),
+        };
+
+        let mut namespaces = HashMap::<String, wasmer::Exports>::new();
+
+        for import in module.imports() {
+            let function_type = match import.ty() {
+                wasmer::ExternType::Function(function_type) => function_type,
+                _ => continue,
+            };
+
+            if self
+                .inner
+                .get_export(import.module(), import.name())
+                .is_some()
+            {
+                continue;
+            }
+
+            let function = match fallback.call1((
+                import.module(),
+                import.name(),
+                FunctionType::from(function_type),
+            )) {
+                Ok(function) if function.is_none() => {
+                    declined_imports.push(format!(
+                        "`{}`.`{}` (returned `None`)",
+                        import.module(),
+                        import.name()
+                    ));
+
+                    continue;
+                }
+                Ok(function) => function,
+                // Only an `Exception` declines the import, others, like
+                // `KeyboardInterrupt`, are propagated.
+                Err(error) if error.is_instance::<PyException>(py) => {
+                    declined_imports.push(format!(
+                        "`{}`.`{}` ({}: {})",
+                        import.module(),
+                        import.name(),
+                        error.ptype(py).name()?,
+                        error.instance(py)
+                    ));
+
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+
+            let function = function.downcast::<PyCell<Function>>().map_err(|_| {
+                to_py_err::<PyTypeError, _>(format!(
+                    "The fallback must return a `Function` or `None` for the import `{}`.`{}`, got `{}`",
+                    import.module(),
+                    import.name(),
+                    function.get_type().name().unwrap_or("unknown")
+                ))
+            })?;
+
+            namespaces
+                .entry(import.module().to_string())
+                .or_default()
+                .insert(import.name(), function.borrow().inner().clone());
+        }
+
+        for (namespace_name, namespace) in namespaces {
+            resolved_imports.register(namespace_name, namespace);
+        }
+
+        Ok((resolved_imports, declined_imports))
+    }
 }
 
 #[pymethods]
@@ -171,6 +275,21 @@ impl ImportObject {
     /// Registers a set of `Function`, `Memory`, `Global` or `Table`
     /// to a particular namespace.
     ///
+    /// An entry can be `None` to deliberately leave an import
+    /// unresolved, e.g. to stub imports one at a time. If the module
+    /// requires it, the instantiation will fail with an error listing
+    /// the imports registered as `None`.
+    ///
+    /// The same `Function` can be registered under several names, or
+    /// in several namespaces: the imports then share one host
+    /// function, without wrapping the callable again.
+    ///
+    /// An entry can also be a `(callable, FunctionType)` tuple: the
+    /// callable is wrapped in a `Function` of the given type, without
+    /// inferring anything from annotations. It is handy for
+    /// un-annotated host functions. In this case, the `store` argument
+    /// is required to create the function.
+    ///
     /// ## Example
     ///
     /// ```py
@@ -186,18 +305,61 @@ impl ImportObject {
     ///     "env",
     ///     {
     ///         "sum": Function(store, sum),
-    ///         "memory": Memory(store, MemoryType(1, shared=False))
+    ///         "memory": Memory(store, MemoryType(1, shared=False)),
+    ///         "not_yet_implemented": None
     ///     }
     /// )
     /// ```
-    #[text_signature = "($self, namespace_name, namespace)"]
-    fn register(&mut self, namespace_name: &str, namespace: &PyDict) -> PyResult<()> {
+    ///
+    /// With a `(callable, FunctionType)` entry:
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, ImportObject, FunctionType, Type
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "env" "sum" (func $sum (param i32 i32) (result i32)))
+    ///       (func (export "add_one") (param i32) (result i32)
+    ///         local.get 0
+    ///         i32.const 1
+    ///         call $sum))
+    ///     """
+    /// )
+    ///
+    /// import_object = ImportObject()
+    /// import_object.register(
+    ///     "env",
+    ///     {
+    ///         "sum": (lambda x, y: x + y, FunctionType([Type.I32, Type.I32], [Type.I32]))
+    ///     },
+    ///     store=store
+    /// )
+    ///
+    /// instance = Instance(module, import_object)
+    ///
+    /// assert instance.exports.add_one(41) == 42
+    /// ```
+    #[text_signature = "($self, namespace_name, namespace, store)"]
+    #[args(store = "None")]
+    fn register(
+        &mut self,
+        py: Python,
+        namespace_name: &str,
+        namespace: &PyDict,
+        store: Option<&Store>,
+    ) -> PyResult<()> {
         let mut wasmer_namespace = wasmer::Exports::new();
+        let mut unresolved_imports = Vec::new();
 
         for (name, item) in namespace.into_iter() {
             let name = String::from(name.downcast::<PyString>().map_err(PyErr::from)?.to_str()?);
 
-            if let Ok(function) = item.downcast::<PyCell<Function>>() {
+            if item.is_none() {
+                unresolved_imports.push((namespace_name.to_string(), name));
+            } else if let Ok(function) = item.downcast::<PyCell<Function>>() {
                 let function = function.borrow();
 
                 wasmer_namespace.insert(name, function.inner().clone());
@@ -213,6 +375,33 @@ impl ImportObject {
                 let table = table.borrow();
 
                 wasmer_namespace.insert(name, table.inner().clone());
+            } else if let Some((py_function, function_type)) = item
+                .downcast::<PyTuple>()
+                .ok()
+                .filter(|tuple| tuple.len() == 2)
+                .and_then(|tuple| {
+                    let function_type =
+                        tuple.get_item(1).downcast::<PyCell<FunctionType>>().ok()?;
+
+                    Some((tuple.get_item(0), function_type))
+                })
+            {
+                let store = store.ok_or_else(|| {
+                    to_py_err::<PyTypeError, _>(format!(
+                        "A `Store` is required to register the `(callable, FunctionType)` entry `{}`",
+                        name
+                    ))
+                })?;
+                let function = Function::new(
+                    py,
+                    store,
+                    py_function,
+                    Some(&function_type.borrow()),
+                    false,
+                    false,
+                )?;
+
+                wasmer_namespace.insert(name, function.inner().clone());
             } else {
                 return Err(to_py_err::<PyTypeError, _>(format!(
                     "`ImportObject` cannot register the given type `{:?}`",
@@ -223,6 +412,83 @@ impl ImportObject {
 
         self.inner.register(namespace_name, wasmer_namespace);
 
+        // The namespace is replaced, and so are its unresolved imports.
+        self.unresolved_imports
+            .retain(|(unresolved_namespace_name, _)| unresolved_namespace_name != namespace_name);
+        self.unresolved_imports.extend(unresolved_imports);
+
+        Ok(())
+    }
+
+    /// Sets a callable that resolves the function imports that are
+    /// not registered, for example to proxy or to mock them. `None`
+    /// removes it.
+    ///
+    /// When a module is instantiated, the fallback is called for each
+    /// missing function import with its namespace name, its name and
+    /// its `FunctionType`, and must return a `Function` of that type.
+    /// It can decline an import by returning `None` or by raising an
+    /// `Exception`: if the module requires it, the instantiation then
+    /// fails with a `RuntimeError` listing the declined imports. The
+    /// imports registered as `None` are missing too, so the fallback
+    /// is called for them. Other exceptions, like
+    /// `KeyboardInterrupt`, are propagated by the instantiation.
+    ///
+    /// A `TypeError` is raised if `fallback` is neither a callable
+    /// nor `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, ImportObject
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "env" "sum" (func $sum (param i32 i32) (result i32)))
+    ///       (func (export "add_one") (param i32) (result i32)
+    ///         local.get 0
+    ///         i32.const 1
+    ///         call $sum))
+    ///     """
+    /// )
+    ///
+    /// def fallback_for(store):
+    ///     from wasmer import Function
+    ///
+    ///     def fallback(namespace_name, name, function_type):
+    ///         if (namespace_name, name) == ("env", "sum"):
+    ///             return Function(store, lambda x, y: x + y, function_type)
+    ///
+    ///         raise LookupError(f"unknown import {namespace_name}.{name}")
+    ///
+    ///     return fallback
+    ///
+    /// import_object = ImportObject()
+    /// import_object.set_fallback(fallback_for(store))
+    ///
+    /// instance = Instance(module, import_object)
+    ///
+    /// assert instance.exports.add_one(41) == 42
+    /// ```
+    #[text_signature = "($self, fallback)"]
+    fn set_fallback(&mut self, fallback: &PyAny) -> PyResult<()> {
+        if fallback.is_none() {
+            self.fallback = None;
+
+            return Ok(());
+        }
+
+        if !fallback.is_callable() {
+            return Err(to_py_err::<PyTypeError, _>(
+                "The fallback must be a callable",
+            ));
+        }
+
+        self.fallback = Some(fallback.into());
+
         Ok(())
     }
 }
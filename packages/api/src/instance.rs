@@ -1,8 +1,18 @@
 use crate::{
-    errors::to_py_err, exports::Exports, import_object::ImportObject, module::Module,
-    wasmer_inner::wasmer,
+    errors::to_py_err,
+    exports::Exports,
+    externals::{Function, Memory},
+    import_object::ImportObject,
+    module::Module,
+    store::InstanceSlot,
+    wasmer_inner::wasmer::{self, ChainableNamedResolver},
 };
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
+use pyo3::{
+    exceptions::{PyLookupError, PyOverflowError, PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyString, PyTuple},
+};
+use std::{cell::Cell, convert::TryFrom, rc::Rc};
 
 /// A WebAssembly instance is a stateful, executable instance of a
 /// WebAssembly `Module`.
@@ -85,43 +95,165 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*};
 #[pyclass(unsendable)]
 #[text_signature = "(module, import_object)"]
 pub struct Instance {
-    #[allow(unused)]
-    inner: wasmer::Instance,
+    /// The instance and its exports, or `None` once it has been
+    /// closed with `Instance.close`.
+    inner: Option<(wasmer::Instance, Py<Exports>)>,
 
-    /// The exports of the instance, as an object of kind `Exports`.
-    ///
-    /// ## Example
-    ///
-    /// See the `Exports` class.
-    #[pyo3(get)]
-    exports: Py<Exports>,
+    /// The slot of the instance in the live instances of the store,
+    /// see `Store(max_instances=N)`. It is released on close.
+    slot: Option<InstanceSlot>,
+
+    /// The peak stack usage of the most recent call, reported by the
+    /// exported functions, see `Instance.last_call_stack_usage`.
+    stack_usage: Rc<Cell<Option<usize>>>,
+
+    /// The names of the exported allocator and deallocator functions,
+    /// used by `Instance.pass_string` and `Instance.free_string`.
+    allocator: String,
+    deallocator: String,
+
+    /// The module and the import object the instance has been
+    /// created with, to create fresh instances with `Instance.fresh`.
+    /// They are dropped on close, with the host functions of the
+    /// import object.
+    module: Option<Module>,
+    import_object: Option<ImportObject>,
+}
+
+fn closed_error() -> PyErr {
+    to_py_err::<PyRuntimeError, _>(
+        "The instance is closed, it cannot be used after `Instance.close`",
+    )
 }
 
 pub enum InstanceError {
     InstantiationError(wasmer::InstantiationError),
+    UnresolvedImports(wasmer::InstantiationError, Vec<String>),
+    DeclinedImports(wasmer::InstantiationError, Vec<String>),
     PyErr(PyErr),
 }
 
+impl From<InstanceError> for PyErr {
+    fn from(error: InstanceError) -> Self {
+        match error {
+            InstanceError::InstantiationError(error) => to_py_err::<PyRuntimeError, _>(error),
+            InstanceError::UnresolvedImports(error, imports) => {
+                to_py_err::<PyRuntimeError, _>(format!(
+                    "{} (the module requires imports registered as `None`: {})",
+                    error,
+                    imports.join(", ")
+                ))
+            }
+            InstanceError::DeclinedImports(error, imports) => {
+                to_py_err::<PyRuntimeError, _>(format!(
+                    "{} (the fallback declined imports required by the module: {})",
+                    error,
+                    imports.join(", ")
+                ))
+            }
+            InstanceError::PyErr(error) => error,
+        }
+    }
+}
+
 impl Instance {
     pub fn raw_new(
         py: Python,
         module: &Module,
         import_object: Option<&ImportObject>,
     ) -> Result<Self, InstanceError> {
+        let slot = module
+            .instance_limit()
+            .acquire()
+            .map_err(InstanceError::PyErr)?;
+        let instance_module = module.clone();
         let module = module.inner();
 
-        let instance = match import_object {
-            Some(import_object) => wasmer::Instance::new(&module, import_object.inner()),
-            None => wasmer::Instance::new(&module, &wasmer::imports! {}),
+        let (instance, declined_imports) = match import_object {
+            Some(import_object) => {
+                let (fallback_imports, declined_imports) = import_object
+                    .resolve_with_fallback(py, module)
+                    .map_err(InstanceError::PyErr)?;
+
+                (
+                    wasmer::Instance::new(
+                        &module,
+                        &import_object.inner().clone().chain_back(fallback_imports),
+                    ),
+                    declined_imports,
+                )
+            }
+            None => (
+                wasmer::Instance::new(&module, &wasmer::imports! {}),
+                Vec::new(),
+            ),
         };
-        let instance = instance.map_err(InstanceError::InstantiationError)?;
+        let instance = instance.map_err(|error| {
+            if !declined_imports.is_empty() {
+                return InstanceError::DeclinedImports(error, declined_imports);
+            }
 
-        let exports =
-            Py::new(py, Exports::new(instance.exports.clone())).map_err(InstanceError::PyErr)?;
+            let needed_unresolved_imports = import_object
+                .map(|import_object| {
+                    module
+                        .imports()
+                        .filter(|import| {
+                            import_object.unresolved_imports().iter().any(
+                                |(namespace_name, name)| {
+                                    namespace_name == import.module() && name == import.name()
+                                },
+                            )
+                        })
+                        .map(|import| format!("`{}`.`{}`", import.module(), import.name()))
+                        .collect::<Vec<String>>()
+                })
+                .unwrap_or_default();
+
+            if needed_unresolved_imports.is_empty() {
+                InstanceError::InstantiationError(error)
+            } else {
+                InstanceError::UnresolvedImports(error, needed_unresolved_imports)
+            }
+        })?;
+
+        let stack_usage = Rc::new(Cell::new(None));
+        let exports = Exports::new(
+            instance.exports.clone(),
+            instance_module.strict_marshaling(),
+            stack_usage.clone(),
+            instance_module.interrupts().clone(),
+        );
+        let (allocator, deallocator) = exports.allocator_names().unwrap_or(("malloc", "free"));
+        let exports = Py::new(py, exports).map_err(InstanceError::PyErr)?;
 
         Ok(Instance {
-            inner: instance,
-            exports,
+            inner: Some((instance, exports)),
+            slot: Some(slot),
+            stack_usage,
+            allocator: allocator.to_string(),
+            deallocator: deallocator.to_string(),
+            module: Some(instance_module),
+            import_object: import_object.cloned(),
+        })
+    }
+
+    fn inner_and_exports(&self) -> PyResult<&(wasmer::Instance, Py<Exports>)> {
+        self.inner.as_ref().ok_or_else(closed_error)
+    }
+
+    fn inner(&self) -> PyResult<&wasmer::Instance> {
+        Ok(&self.inner_and_exports()?.0)
+    }
+
+    /// Returns the exported function named `name`, used as an
+    /// allocator or a deallocator by the string helpers.
+    fn allocator_function(&self, name: &str, role: &str) -> PyResult<&wasmer::Function> {
+        self.inner()?.exports.get_function(name).map_err(|_| {
+            to_py_err::<PyLookupError, _>(format!(
+                "The instance has no function exported as `{}` to be used as the {}; \
+                 set the allocator exports with `Instance.set_allocator`",
+                name, role
+            ))
         })
     }
 }
@@ -130,9 +262,455 @@ impl Instance {
 impl Instance {
     #[new]
     fn new(py: Python, module: &Module, import_object: Option<&ImportObject>) -> PyResult<Self> {
-        Instance::raw_new(py, &module, import_object).map_err(|error| match error {
-            InstanceError::InstantiationError(error) => to_py_err::<PyRuntimeError, _>(error),
-            InstanceError::PyErr(error) => error,
-        })
+        Ok(Instance::raw_new(py, &module, import_object)?)
+    }
+
+    /// The exports of the instance, as an object of kind `Exports`.
+    ///
+    /// ## Example
+    ///
+    /// See the `Exports` class.
+    #[getter]
+    fn exports(&self, py: Python) -> PyResult<Py<Exports>> {
+        Ok(self.inner_and_exports()?.1.clone_ref(py))
+    }
+
+    /// Calls the exported function named `name` with `arguments`, in
+    /// one step. It is the same as `getattr(instance.exports,
+    /// name)(*arguments)`, and is handy when the name comes from
+    /// data. The arguments are checked as for any call to a
+    /// `Function`.
+    ///
+    /// A `LookupError` is raised if the export does not exist, and a
+    /// `TypeError` if it is not a function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// assert instance.invoke("sum", 1, 2) == 3
+    /// ```
+    #[text_signature = "($self, name, *arguments)"]
+    #[args(arguments = "*")]
+    fn invoke(&self, py: Python, name: &str, arguments: &PyTuple) -> PyResult<PyObject> {
+        let exports = self.inner_and_exports()?.1.as_ref(py).borrow();
+        let export = exports.get(py, name.to_string())?;
+        let function = export
+            .as_ref(py)
+            .downcast::<PyCell<Function>>()
+            .map_err(|_| {
+                to_py_err::<PyTypeError, _>(format!("Export `{}` is not a function", name))
+            })?;
+
+        let function = function.borrow();
+
+        function.call(py, arguments)
+    }
+
+    /// Calls the exported function named `name` with `arguments`,
+    /// like `Instance.invoke`, for a function returning an array in
+    /// the exported memory (`memory` by default), and returns a
+    /// NumPy array of the NumPy `dtype` over this array. NumPy must
+    /// be installed.
+    ///
+    /// The `convention` keyword argument tells how to read the
+    /// results of the function:
+    ///
+    /// * `"pointer_length"` (the default), the function returns the
+    ///   pointer to the array and its number of items,
+    /// * `"length_pointer"`, the function returns the number of
+    ///   items and the pointer,
+    /// * or a callable, that receives the results of the function
+    ///   and returns the `(pointer, length)` tuple, e.g. when the
+    ///   length is a number of bytes rather than items.
+    ///
+    /// The NumPy array shares the memory data, so no data is copied.
+    /// Like any buffer export, it prevents the memory from growing
+    /// until it is released. An `IndexError` is raised if the array
+    /// does not fit in the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py,ignore
+    /// import numpy
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     r"""
+    ///     (module
+    ///       (memory (export "memory") 1)
+    ///       (data (i32.const 16) "\01\00\00\00\02\00\00\00\03\00\00\00")
+    ///       (func (export "numbers") (result i32 i32)
+    ///         i32.const 16
+    ///         i32.const 3))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// array = instance.call_returning_array("numbers", numpy.int32)
+    ///
+    /// assert array.tolist() == [1, 2, 3]
+    ///
+    /// # The length is a number of bytes, with a custom convention.
+    /// array = instance.call_returning_array(
+    ///     "numbers",
+    ///     numpy.uint8,
+    ///     convention=lambda results: (results[0], results[1] * 4),
+    /// )
+    ///
+    /// assert len(array) == 12
+    /// ```
+    #[text_signature = "($self, name, dtype, *arguments, memory, convention)"]
+    #[args(arguments = "*", memory = "\"memory\"", convention = "None")]
+    fn call_returning_array(
+        &self,
+        py: Python,
+        name: &str,
+        dtype: &PyAny,
+        arguments: &PyTuple,
+        memory: &str,
+        convention: Option<&PyAny>,
+    ) -> PyResult<PyObject> {
+        let results = self.invoke(py, name, arguments)?;
+        let results = results.as_ref(py);
+        let convention = convention.unwrap_or_else(|| PyString::new(py, "pointer_length").as_ref());
+
+        let (pointer, length) = if convention.is_callable() {
+            convention.call1((results,))?.extract::<(usize, usize)>()?
+        } else {
+            let swap = match convention.extract::<&str>() {
+                Ok("pointer_length") => false,
+                Ok("length_pointer") => true,
+                _ => {
+                    return Err(to_py_err::<PyValueError, _>(format!(
+                        "The convention must be `\"pointer_length\"`, `\"length_pointer\"` or a callable, got `{}`",
+                        convention
+                    )))
+                }
+            };
+            let (first, second) = results.extract::<(i32, i32)>().map_err(|_| {
+                to_py_err::<PyTypeError, _>(format!(
+                    "The function `{}` must return 2 `i32` results for the `{}` convention, got `{}`",
+                    name, convention, results
+                ))
+            })?;
+            // The `i32` results are reinterpreted as unsigned numbers.
+            let (first, second) = (first as u32 as usize, second as u32 as usize);
+
+            if swap {
+                (second, first)
+            } else {
+                (first, second)
+            }
+        };
+
+        let memory = self
+            .inner()?
+            .exports
+            .get_memory(memory)
+            .map_err(to_py_err::<PyLookupError, _>)?;
+
+        Memory::raw_new(memory.clone()).numpy_array(py, dtype, pointer, length)
+    }
+
+    /// The peak stack usage, in bytes, of the most recent call to an
+    /// exported function of the instance, or `None` if it is
+    /// unavailable. It helps to size a stack limit with a
+    /// measurement rather than a guess.
+    ///
+    /// Wasmer cannot inspect the stack of running WebAssembly code,
+    /// so the stack is measured each time the WebAssembly code calls
+    /// a host function: the peak is a lower bound, and it is `None`
+    /// for a call that does not call any host function. Only calls
+    /// made from Python are measured, not the nested ones made by a
+    /// host function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, ImportObject, Function
+    ///
+    /// def probe():
+    ///     pass
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "env" "probe" (func $probe))
+    ///       (func (export "run")
+    ///         call $probe)
+    ///       (func (export "nothing")))
+    ///     """
+    /// )
+    ///
+    /// import_object = ImportObject()
+    /// import_object.register("env", {"probe": Function(store, probe)})
+    ///
+    /// instance = Instance(module, import_object)
+    ///
+    /// assert instance.last_call_stack_usage == None
+    ///
+    /// instance.exports.run()
+    ///
+    /// assert instance.last_call_stack_usage > 0
+    ///
+    /// instance.exports.nothing()
+    ///
+    /// assert instance.last_call_stack_usage == None
+    /// ```
+    #[getter]
+    fn last_call_stack_usage(&self) -> Option<usize> {
+        self.stack_usage.get()
+    }
+
+    /// Closes the instance: it drops its references to the
+    /// WebAssembly instance, its exports, its module and its import
+    /// object (and so to the host functions of the import object),
+    /// and the instance cannot be used anymore (a `RuntimeError` is
+    /// then raised). The underlying resources are freed once nothing
+    /// else refers to them, e.g. an exported function or memory kept
+    /// by the caller.
+    /// The instance also stops counting against the limit of
+    /// `Store(max_instances=N)`.
+    ///
+    /// It returns `True` the first time, and `False` if the instance
+    /// is already closed, so it is safe to call it several times.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// instance = Instance(Module(Store(), '(module)'))
+    ///
+    /// assert instance.close() == True
+    /// assert instance.close() == False
+    ///
+    /// try:
+    ///     instance.exports
+    /// except RuntimeError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    #[text_signature = "($self)"]
+    fn close(&mut self) -> bool {
+        self.slot = None;
+        self.module = None;
+        self.import_object = None;
+
+        self.inner.take().is_some()
+    }
+
+    /// Passes a string to the instance: it allocates `len(bytes)`
+    /// bytes with the exported allocator (the one found by
+    /// `Exports.find_allocator`, or `malloc`, by default, see
+    /// `Instance.set_allocator`), writes the UTF-8 bytes of the
+    /// string in the exported memory (`memory` by default), and
+    /// returns the pointer to them. No NUL byte is written. The
+    /// string must be released with `Instance.free_string`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (memory (export "memory") 1)
+    ///       (global $next (mut i32) (i32.const 16))
+    ///
+    ///       ;; A bump allocator.
+    ///       (func (export "malloc") (param $size i32) (result i32)
+    ///         (local $pointer i32)
+    ///         global.get $next
+    ///         local.set $pointer
+    ///         global.get $next
+    ///         local.get $size
+    ///         i32.add
+    ///         global.set $next
+    ///         local.get $pointer)
+    ///
+    ///       (func (export "free") (param i32))
+    ///
+    ///       (func (export "first_byte") (param i32) (result i32)
+    ///         local.get 0
+    ///         i32.load8_u))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// pointer = instance.pass_string('Wasmer')
+    ///
+    /// assert pointer == 16
+    /// assert instance.exports.first_byte(pointer) == ord('W')
+    /// assert bytes(instance.exports.memory.uint8_view(pointer)[0:6]) == b'Wasmer'
+    ///
+    /// instance.free_string(pointer)
+    /// ```
+    #[text_signature = "($self, string, allocator, memory)"]
+    #[args(allocator = "None", memory = "\"memory\"")]
+    fn pass_string(&self, string: &str, allocator: Option<&str>, memory: &str) -> PyResult<u32> {
+        let allocator =
+            self.allocator_function(allocator.unwrap_or(self.allocator.as_str()), "allocator")?;
+        let memory = self
+            .inner()?
+            .exports
+            .get_memory(memory)
+            .map_err(to_py_err::<PyLookupError, _>)?;
+        let bytes = string.as_bytes();
+        let length = i32::try_from(bytes.len()).map_err(|_| {
+            to_py_err::<PyOverflowError, _>(format!(
+                "The string is too long to be passed ({} bytes)",
+                bytes.len()
+            ))
+        })?;
+
+        let pointer = match allocator
+            .call(&[wasmer::Value::I32(length)])
+            .map_err(to_py_err::<PyRuntimeError, _>)?
+            .as_ref()
+        {
+            [wasmer::Value::I32(pointer)] => *pointer as u32,
+            results => {
+                return Err(to_py_err::<PyRuntimeError, _>(format!(
+                    "The allocator must return a single `i32` pointer, got `{:?}`",
+                    results
+                )))
+            }
+        };
+
+        if let Err(error) = Memory::raw_new(memory.clone()).write_bytes(pointer as usize, bytes) {
+            // Do not leak the allocation. The write error matters
+            // more than a failure of the deallocator, if any.
+            let _ = self.free_string(pointer, None);
+
+            return Err(error);
+        }
+
+        Ok(pointer)
+    }
+
+    /// Releases a string passed with `Instance.pass_string`, by
+    /// calling the exported deallocator (the one found by
+    /// `Exports.find_allocator`, or `free`, by default, see
+    /// `Instance.set_allocator`) with its pointer.
+    ///
+    /// ## Example
+    ///
+    /// See `Instance.pass_string`.
+    #[text_signature = "($self, pointer, deallocator)"]
+    #[args(deallocator = "None")]
+    fn free_string(&self, pointer: u32, deallocator: Option<&str>) -> PyResult<()> {
+        self.allocator_function(
+            deallocator.unwrap_or(self.deallocator.as_str()),
+            "deallocator",
+        )?
+        .call(&[wasmer::Value::I32(pointer as i32)])
+        .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        Ok(())
+    }
+
+    /// Sets the names of the exported allocator and deallocator
+    /// functions used by `Instance.pass_string` and
+    /// `Instance.free_string`, once for all, for modules whose
+    /// exports do not follow a convention known by
+    /// `Exports.find_allocator`. The names are not checked until the
+    /// functions are needed.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (memory (export "memory") 1)
+    ///       (func (export "__wbindgen_malloc") (param i32) (result i32)
+    ///         i32.const 16)
+    ///       (func (export "__wbindgen_free") (param i32)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// instance.set_allocator('__wbindgen_malloc', '__wbindgen_free')
+    ///
+    /// pointer = instance.pass_string('Wasmer')
+    ///
+    /// assert pointer == 16
+    ///
+    /// instance.free_string(pointer)
+    /// ```
+    #[text_signature = "($self, allocator, deallocator)"]
+    fn set_allocator(&mut self, allocator: String, deallocator: String) {
+        self.allocator = allocator;
+        self.deallocator = deallocator;
+    }
+
+    /// Creates a new instance of the same module, with the same
+    /// import object, e.g. to reset the state of the instance between
+    /// two fuzzing runs or two tests. The module is not compiled
+    /// again, so it is much cheaper than creating a new `Module`.
+    ///
+    /// The new instance has its own memories, tables and globals, as
+    /// defined by the module. However, the imports are shared, since
+    /// they come from the same import object: a memory or a global
+    /// imported by the module is the same for both instances. For
+    /// WASI, a new import object should be generated and given to
+    /// `Instance(module, import_object)` instead. The allocator
+    /// exports set with `Instance.set_allocator` are kept.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (global $counter (export "counter") (mut i32) (i32.const 0))
+    ///       (func (export "increment")
+    ///         (global.set $counter (i32.add (global.get $counter) (i32.const 1)))))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// instance.exports.increment()
+    ///
+    /// assert instance.exports.counter.value == 1
+    ///
+    /// fresh_instance = instance.fresh()
+    ///
+    /// assert fresh_instance.exports.counter.value == 0
+    /// assert instance.exports.counter.value == 1
+    /// ```
+    #[text_signature = "($self)"]
+    fn fresh(&self, py: Python) -> PyResult<Self> {
+        let module = self.module.as_ref().ok_or_else(closed_error)?;
+        let mut instance = Instance::raw_new(py, module, self.import_object.as_ref())?;
+        instance.allocator = self.allocator.clone();
+        instance.deallocator = self.deallocator.clone();
+
+        Ok(instance)
     }
 }
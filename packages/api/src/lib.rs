@@ -1,16 +1,18 @@
 use pyo3::{
     prelude::*,
     types::{PyBytes, PyTuple},
-    wrap_pymodule,
+    wrap_pyfunction, wrap_pymodule,
 };
 
 pub(crate) mod wasmer_inner {
     pub use wasmer;
     pub use wasmer_engines;
     pub use wasmer_types;
+    pub use wasmer_vm;
     pub use wasmer_wasi;
 }
 
+mod convert;
 mod engines;
 mod errors;
 mod exports;
@@ -120,6 +122,72 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
         wat::wasm2wat(bytes)
     }
 
+    /// Rewrites WebAssembly bytes so that an import is renamed, both
+    /// its namespace and its name. It is handy to adapt a module to
+    /// another host without recompiling its sources, e.g. to move an
+    /// import from `env` to `wasi_snapshot_preview1`. A `LookupError`
+    /// is raised if the module has no such import.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, wat2wasm, rename_import
+    ///
+    /// bytes = wat2wasm('(module (import "env" "print" (func)))')
+    /// bytes = rename_import(bytes, 'env', 'print', 'host', 'log')
+    ///
+    /// import_ = Module(Store(), bytes).imports[0]
+    ///
+    /// assert import_.module == 'host'
+    /// assert import_.name == 'log'
+    /// ```
+    #[pyfn(module, "rename_import")]
+    #[text_signature = "(bytes, old_namespace, old_name, new_namespace, new_name)"]
+    fn rename_import<'py>(
+        py: Python<'py>,
+        bytes: &PyBytes,
+        old_namespace: &str,
+        old_name: &str,
+        new_namespace: &str,
+        new_name: &str,
+    ) -> PyResult<&'py PyBytes> {
+        module::rename_import(
+            py,
+            bytes,
+            (old_namespace, old_name),
+            (new_namespace, new_name),
+        )
+    }
+
+    /// Checks whether two memories have the same contents, i.e. the
+    /// same size and the same bytes. It is handy in tests, e.g. to
+    /// check that two instances end in the same state. The bytes are
+    /// compared at once, without copying them.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType, memories_equal
+    ///
+    /// store = Store()
+    /// a = Memory(store, MemoryType(1, shared=False))
+    /// b = Memory(store, MemoryType(1, shared=False))
+    ///
+    /// assert memories_equal(a, b)
+    ///
+    /// a.uint8_view()[42] = 1
+    ///
+    /// assert not memories_equal(a, b)
+    ///
+    /// # Memories of different sizes are never equal.
+    /// assert not memories_equal(a, Memory(store, MemoryType(2, shared=False)))
+    /// ```
+    #[pyfn(module, "memories_equal")]
+    #[text_signature = "(a, b)"]
+    fn memories_equal(a: &externals::Memory, b: &externals::Memory) -> bool {
+        a.has_same_contents(b)
+    }
+
     // Classes.
     module.add_class::<exports::Exports>()?;
     module.add_class::<externals::Function>()?;
@@ -145,10 +213,20 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     module.add_class::<types::TableType>()?;
     module.add_class::<values::Value>()?;
 
-    // Enums.
+    // Exceptions.
+    module.add("HostTrap", py.get_type::<errors::HostTrap>())?;
     module.add(
-        "Type",
-        enum_module.call1(
+        "CallTimeoutError",
+        py.get_type::<errors::CallTimeoutError>(),
+    )?;
+    module.add(
+        "CallInterruptedError",
+        py.get_type::<errors::CallInterruptedError>(),
+    )?;
+
+    // Enums.
+    {
+        let type_enum = enum_module.call1(
             "IntEnum",
             PyTuple::new(
                 py,
@@ -161,10 +239,88 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
                         .as_str(),
                 ],
             ),
-        )?,
-    )?;
+        )?;
+
+        // Add the `Type.is_number()` and `Type.is_reference()`
+        // predicates, e.g. `Type.I32.is_number()`.
+        #[pyfunction]
+        fn is_number(ty: types::Type) -> bool {
+            ty.is_number()
+        }
+
+        #[pyfunction]
+        fn is_reference(ty: types::Type) -> bool {
+            ty.is_reference()
+        }
+
+        let partial_method = py.import("functools")?.getattr("partialmethod")?;
+
+        type_enum.setattr(
+            "is_number",
+            partial_method.call1((wrap_pyfunction!(is_number, module)?,))?,
+        )?;
+        type_enum.setattr(
+            "is_reference",
+            partial_method.call1((wrap_pyfunction!(is_reference, module)?,))?,
+        )?;
+
+        // Add the `Type.byte_size` property, e.g. `Type.I32.byte_size
+        // == 4`, or `None` for a reference type.
+        #[pyfunction]
+        fn byte_size(ty: types::Type) -> Option<usize> {
+            ty.byte_size()
+        }
+
+        type_enum.setattr(
+            "byte_size",
+            py.import("builtins")?
+                .getattr("property")?
+                .call1((wrap_pyfunction!(byte_size, module)?,))?,
+        )?;
+
+        // A stable and readable representation, e.g. `Type.I32`
+        // instead of `<Type.I32: 1>`; so that `repr([Type.I32,
+        // Type.F64])` is `[Type.I32, Type.F64]`.
+        #[pyfunction]
+        fn repr(ty: types::Type) -> String {
+            format!("Type.{}", <&str>::from(&ty))
+        }
+
+        type_enum.setattr(
+            "__repr__",
+            partial_method.call1((wrap_pyfunction!(repr, module)?,))?,
+        )?;
+
+        // The module may be initialized more than once, and the enum
+        // of the first initialization is kept.
+        let _ = types::TYPE_ENUM.set(py, type_enum.into());
+
+        module.add("Type", type_enum)?;
+    }
+
+    {
+        let export_kind_enum = enum_module.call1(
+            "IntEnum",
+            PyTuple::new(
+                py,
+                [
+                    "ExportKind",
+                    exports::ExportKind::iter()
+                        .map(Into::into)
+                        .collect::<Vec<&'static str>>()
+                        .join(" ")
+                        .as_str(),
+                ],
+            ),
+        )?;
+
+        let _ = exports::EXPORT_KIND_ENUM.set(py, export_kind_enum.into());
+
+        module.add("ExportKind", export_kind_enum)?;
+    }
 
     // Modules.
+    module.add_wrapped(wrap_pymodule!(convert))?;
     module.add_wrapped(wrap_pymodule!(engine))?;
     module.add_wrapped(wrap_pymodule!(target))?;
     module.add_wrapped(wrap_pymodule!(wasi))?;
@@ -172,6 +328,72 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     Ok(())
 }
 
+/// Conversions between Python objects and WebAssembly values.
+///
+/// The conversions are the ones used to pass the arguments and the
+/// results of the WebAssembly functions. They are handy to build
+/// custom calling conventions on top of them.
+///
+/// Only numeric types (`I32`, `I64`, `F32`, `F64` and `V128`) can be
+/// converted; reference types raise a `TypeError`.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import convert, Type, Value
+///
+/// value = convert.to_wasm(42, Type.I64)
+///
+/// assert isinstance(value, Value)
+/// assert repr(value) == 'I64(42)'
+/// assert convert.to_python(value) == 42
+/// ```
+#[pymodule]
+fn convert(_py: Python, module: &PyModule) -> PyResult<()> {
+    // Functions.
+
+    /// Converts a Python object to a WebAssembly `Value` of the
+    /// given `Type`.
+    ///
+    /// Integer types expect an `int` (or a subclass of it, like
+    /// `bool`), float types expect a `float`, otherwise a `TypeError`
+    /// is raised. A value that does not fit in the given type raises
+    /// an `OverflowError`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import convert, Type
+    ///
+    /// assert repr(convert.to_wasm(42, Type.I32)) == 'I32(42)'
+    /// assert repr(convert.to_wasm(4.2, Type.F64)) == 'F64(4.2)'
+    /// ```
+    #[pyfn(module, "to_wasm")]
+    #[text_signature = "(value, type)"]
+    fn to_wasm(value: &PyAny, r#type: types::Type) -> PyResult<values::Value> {
+        convert::to_wasm(value, r#type)
+    }
+
+    /// Converts a WebAssembly `Value` to a Python object: `int` for
+    /// integer types, `float` for float types.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import convert, Value
+    ///
+    /// assert convert.to_python(Value.i32(42)) == 42
+    /// assert convert.to_python(Value.f64(4.2)) == 4.2
+    /// ```
+    #[pyfn(module, "to_python")]
+    #[text_signature = "(value)"]
+    fn to_python(py: Python, value: &values::Value) -> PyResult<PyObject> {
+        convert::to_python(py, value)
+    }
+
+    Ok(())
+}
+
 /// Wasmer Engines.
 ///
 /// Engines are mainly responsible for two things:
@@ -215,6 +437,16 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
 /// engine = engine.JIT(Compiler)
 /// ```
 ///
+/// Create a JIT engine with the Cranelift compiler, where NaNs are
+/// canonicalized so that float results are the same on all hosts:
+///
+/// ```py
+/// from wasmer import engine
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// engine = engine.JIT(Compiler, canonicalize_nans=True)
+/// ```
+///
 /// Engines are stored inside the `wasmer.Store`.
 #[pymodule]
 fn engine(_py: Python, module: &PyModule) -> PyResult<()> {
@@ -320,6 +552,40 @@ fn target(_py: Python, module: &PyModule) -> PyResult<()> {
 /// # Here we go, let's start the program.
 /// instance.exports._start()
 /// ```
+///
+/// When the program calls `proc_exit`, e.g. with `exit(1)`, the
+/// `WasiExit` exception is raised. Its `code` attribute holds the
+/// exit code of the program:
+///
+/// ```py
+/// from wasmer import wasi, Store, Module, Instance
+///
+/// store = Store()
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+///       (memory (export "memory") 1)
+///       (func (export "_start")
+///         i32.const 1
+///         call $proc_exit))
+///     """
+/// )
+///
+/// wasi_env = wasi.StateBuilder('test-program').finalize()
+/// import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+/// instance = Instance(module, import_object)
+///
+/// try:
+///     instance.exports._start()
+/// except wasi.WasiExit as exit:
+///     assert exit.code == 1
+/// else:
+///     assert False
+/// ```
+///
+/// Also, `Environment.run` runs a program and returns its exit code.
 #[pymodule]
 fn wasi(py: Python, module: &PyModule) -> PyResult<()> {
     let enum_module = py.import("enum")?;
@@ -343,6 +609,9 @@ fn wasi(py: Python, module: &PyModule) -> PyResult<()> {
     module.add_class::<wasi::Environment>()?;
     module.add_class::<wasi::StateBuilder>()?;
 
+    // Exceptions.
+    module.add("WasiExit", py.get_type::<errors::WasiExit>())?;
+
     // Enums.
     module.add(
         "Version",
@@ -2,7 +2,7 @@ use crate::{errors::to_py_err, wasmer_inner::wasmer};
 use pyo3::{
     class::buffer::PyBufferProtocol,
     exceptions::PyBufferError,
-    ffi::{PyBUF_FORMAT, PyBUF_ND, PyBUF_STRIDES, Py_IncRef, Py_buffer},
+    ffi::{PyBUF_FORMAT, PyBUF_ND, PyBUF_STRIDES, PyBUF_WRITABLE, Py_IncRef, Py_buffer},
     prelude::*,
     pycell::PyRefMut,
     AsPyPointer,
@@ -13,11 +13,13 @@ use std::{
     ops::Deref,
     os::raw::{c_char, c_int},
     ptr,
+    sync::{Mutex, PoisonError},
 };
 
 /// Represents a read-and-write buffer over data of a memory.
 ///
-/// It is built by the `Memory.buffer` getter.
+/// It is built by the `Memory.buffer` getter. A read-only buffer over
+/// a slice of the memory data is built by the `Memory.slice` method.
 ///
 /// It implements the [Python buffer protocol][buffer-protocol], so it
 /// is possible to read and write bytes with [`bytes`][bytes],
@@ -54,14 +56,130 @@ use std::{
 /// assert byte_array[0:3] == b'\x01\x02\x03'
 /// assert byte_array[3:9].decode() == 'Wasmer'
 /// ```
+///
+/// ## Buffers and memory growth
+///
+/// Growing a memory may reallocate it elsewhere, which would leave
+/// any consumer of the buffer (a `memoryview`, a NumPy array built
+/// with `numpy.frombuffer`, etc.) pointing at freed memory. To
+/// prevent that, every export of a buffer is registered against its
+/// memory, and `Memory.grow` raises a `BufferError` as long as an
+/// export is outstanding. The consumer must be released first (with
+/// `memoryview.release()`, or by deleting the NumPy array), exactly
+/// like `bytearray` refuses to be resized while it is exported.
+///
+/// ```py
+/// from wasmer import Memory, MemoryType, Store
+///
+/// store = Store()
+/// memory = Memory(store, MemoryType(1, shared=False))
+///
+/// view = memoryview(memory.buffer)
+///
+/// try:
+///     memory.grow(1)
+/// except BufferError:
+///     pass
+///
+/// view.release()
+/// memory.grow(1)
+///
+/// assert memory.size == 2
+/// ```
+///
+/// This is enforced for the module too: while a buffer is exported,
+/// the `memory.grow` instruction fails and returns -1, as if the
+/// memory had reached its maximum size.
 #[pyclass(unsendable)]
 pub struct Buffer {
     memory: wasmer::Memory,
+    offset: usize,
+    length: Option<usize>,
+    readonly: bool,
+}
+
+/// The number of outstanding buffer exports, per memory. Memories
+/// are identified by the address of their data, which cannot change
+/// while an export is outstanding, since growing is refused.
+static EXPORTED_BUFFERS: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+
+/// Returns the number of outstanding buffer exports over `memory`,
+/// i.e. the number of consumers that hold a pointer into its data.
+pub(crate) fn exported_buffers(memory: &wasmer::Memory) -> usize {
+    exported_buffers_at(memory.data_ptr() as usize)
+}
+
+/// Returns the number of outstanding buffer exports over the memory
+/// whose data starts at `data_ptr`.
+pub(crate) fn exported_buffers_at(data_ptr: usize) -> usize {
+    EXPORTED_BUFFERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|(exported_data_ptr, _)| *exported_data_ptr == data_ptr)
+        .map(|(_, count)| *count)
+        .unwrap_or(0)
+}
+
+fn register_export(memory: &wasmer::Memory) {
+    let data_ptr = memory.data_ptr() as usize;
+    let mut exported_buffers = EXPORTED_BUFFERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    match exported_buffers
+        .iter_mut()
+        .find(|(exported_data_ptr, _)| *exported_data_ptr == data_ptr)
+    {
+        Some((_, count)) => *count += 1,
+        None => exported_buffers.push((data_ptr, 1)),
+    }
+}
+
+fn unregister_export(memory: &wasmer::Memory) {
+    let data_ptr = memory.data_ptr() as usize;
+    let mut exported_buffers = EXPORTED_BUFFERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(position) = exported_buffers
+        .iter()
+        .position(|(exported_data_ptr, _)| *exported_data_ptr == data_ptr)
+    {
+        exported_buffers[position].1 -= 1;
+
+        if exported_buffers[position].1 == 0 {
+            exported_buffers.swap_remove(position);
+        }
+    }
 }
 
 impl Buffer {
     pub fn new(memory: wasmer::Memory) -> Self {
-        Buffer { memory }
+        Buffer {
+            memory,
+            offset: 0,
+            length: None,
+            readonly: false,
+        }
+    }
+
+    pub(crate) fn new_slice(memory: wasmer::Memory, offset: usize, length: usize) -> Self {
+        Buffer {
+            memory,
+            offset,
+            length: Some(length),
+            readonly: false,
+        }
+    }
+
+    pub(crate) fn new_readonly_slice(memory: wasmer::Memory, offset: usize, length: usize) -> Self {
+        Buffer {
+            memory,
+            offset,
+            length: Some(length),
+            readonly: true,
+        }
     }
 }
 
@@ -74,7 +192,26 @@ impl PyBufferProtocol for Buffer {
             ));
         }
 
+        if slf.readonly && PyBUF_WRITABLE == (flags & PyBUF_WRITABLE) {
+            return Err(to_py_err::<PyBufferError, _>("The buffer is read-only"));
+        }
+
         let memory_view = slf.memory.view::<u8>();
+        let offset = slf.offset;
+        let length = slf.length.unwrap_or(memory_view.len());
+
+        if offset
+            .checked_add(length)
+            .filter(|end| *end <= memory_view.len())
+            .is_none()
+        {
+            return Err(to_py_err::<PyBufferError, _>(format!(
+                "Out of bound: The slice `{}:{}` is larger than the memory size {}",
+                offset,
+                offset.saturating_add(length),
+                memory_view.len()
+            )));
+        }
 
         // Fill `Py_buffer` according to https://docs.python.org/3/c-api/buffer.html.
         unsafe {
@@ -86,7 +223,7 @@ impl PyBufferProtocol for Buffer {
             //
             // For contiguous arrays, the value points to the
             // beginning of the memory block.
-            (*view).buf = memory_view.deref().as_ptr() as *mut c_void;
+            (*view).buf = memory_view.deref()[offset..].as_ptr() as *mut c_void;
 
             // A new reference to the exporting object. The reference
             // is owned by the consumer and automatically decremented
@@ -114,7 +251,7 @@ impl PyBufferProtocol for Buffer {
             // is only valid if the buffer has been obtained by a
             // request that guarantees contiguity. In most cases such
             // a request will be `PyBUF_SIMPLE` or `PyBUF_WRITABLE`.
-            (*view).len = memory_view.len() as isize;
+            (*view).len = length as isize;
 
             // An indicator of whether the buffer is read-only. This
             // field is controlled by the `PyBUF_WRITABLE` flag.
@@ -125,9 +262,10 @@ impl PyBufferProtocol for Buffer {
             // read-only or writable buffer, but the choice MUST be
             // consistent for all consumers.
             //
-            // IMPL NOTE: always provide a writable buffer, requests
-            // are allowed to still interpret it as readonly.
-            (*view).readonly = 0;
+            // IMPL NOTE: provide a writable buffer, except for
+            // read-only slices; requests are allowed to still
+            // interpret it as readonly.
+            (*view).readonly = slf.readonly as c_int;
 
             // Item size in bytes of a single element. Same as the
             // value of `struct.calcsize()` called on non-`NULL`
@@ -232,10 +370,14 @@ impl PyBufferProtocol for Buffer {
             (*view).internal = ptr::null_mut();
         }
 
+        register_export(&slf.memory);
+
         Ok(())
     }
 
-    fn bf_releasebuffer(_slf: PyRefMut<Self>, _view: *mut Py_buffer) -> PyResult<()> {
+    fn bf_releasebuffer(slf: PyRefMut<Self>, _view: *mut Py_buffer) -> PyResult<()> {
+        unregister_export(&slf.memory);
+
         Ok(())
     }
 }
@@ -1,5 +1,8 @@
 mod buffer;
+mod tunables;
 mod views;
 
+pub(crate) use buffer::exported_buffers;
 pub use buffer::Buffer;
+pub(crate) use tunables::Tunables;
 pub use views::{Int16Array, Int32Array, Int8Array, Uint16Array, Uint32Array, Uint8Array};
@@ -0,0 +1,115 @@
+use super::buffer::exported_buffers_at;
+use crate::wasmer_inner::{wasmer, wasmer_vm};
+use std::{ptr::NonNull, sync::Arc};
+use wasmer::vm::{MemoryError, MemoryStyle, TableStyle, VMMemoryDefinition, VMTableDefinition};
+
+/// The tunables of every store. They are the `BaseTunables` of
+/// Wasmer, except that the memories they create refuse to grow as
+/// long as one of their buffers is exported, because growing may
+/// move the memory data and leave the consumers of the buffer with a
+/// dangling pointer.
+///
+/// Since the check lives in the memory itself, it holds for the
+/// host (`Memory.grow`) as well as for the guest (the `memory.grow`
+/// instruction, which then returns -1).
+pub(crate) struct Tunables {
+    base: wasmer::BaseTunables,
+}
+
+impl Tunables {
+    pub(crate) fn for_target(target: &wasmer::Target) -> Self {
+        Self {
+            base: wasmer::BaseTunables::for_target(target),
+        }
+    }
+}
+
+impl wasmer::Tunables for Tunables {
+    fn memory_style(&self, memory: &wasmer::MemoryType) -> MemoryStyle {
+        self.base.memory_style(memory)
+    }
+
+    fn table_style(&self, table: &wasmer::TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &wasmer::MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<Arc<dyn wasmer_vm::Memory>, MemoryError> {
+        Ok(Arc::new(GuardedMemory {
+            inner: self.base.create_host_memory(ty, style)?,
+        }))
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &wasmer::MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<Arc<dyn wasmer_vm::Memory>, MemoryError> {
+        Ok(Arc::new(GuardedMemory {
+            inner: self
+                .base
+                .create_vm_memory(ty, style, vm_definition_location)?,
+        }))
+    }
+
+    fn create_host_table(
+        &self,
+        ty: &wasmer::TableType,
+        style: &TableStyle,
+    ) -> Result<Arc<dyn wasmer_vm::Table>, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &wasmer::TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<Arc<dyn wasmer_vm::Table>, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
+}
+
+/// A memory that refuses to grow while one of its buffers is
+/// exported, see `Tunables`.
+#[derive(Debug)]
+struct GuardedMemory {
+    inner: Arc<dyn wasmer_vm::Memory>,
+}
+
+impl wasmer_vm::Memory for GuardedMemory {
+    fn ty(&self) -> &wasmer::MemoryType {
+        self.inner.ty()
+    }
+
+    fn style(&self) -> &MemoryStyle {
+        self.inner.style()
+    }
+
+    fn size(&self) -> wasmer::Pages {
+        self.inner.size()
+    }
+
+    fn grow(&self, delta: wasmer::Pages) -> Result<wasmer::Pages, MemoryError> {
+        if delta.0 > 0 {
+            // SAFETY: `vmmemory` is valid for the lifetime of the memory.
+            let data_ptr = unsafe { self.inner.vmmemory().as_ref().base } as usize;
+
+            if exported_buffers_at(data_ptr) > 0 {
+                return Err(MemoryError::Generic(
+                    "The memory cannot grow while one of its buffers is exported".to_string(),
+                ));
+            }
+        }
+
+        self.inner.grow(delta)
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.inner.vmmemory()
+    }
+}
@@ -5,7 +5,12 @@ use pyo3::{
     prelude::*,
     types::{PyAny, PyInt, PyLong, PySequence, PySlice},
 };
-use std::{cell::Cell, cmp::min, mem::size_of, ops::Range};
+use std::{
+    cell::Cell,
+    cmp::{max, min},
+    mem::size_of,
+    ops::Range,
+};
 
 macro_rules! memory_view {
     ($class_name:ident over $wasm_type:ty | $bytes_per_element:expr) => {
@@ -20,6 +25,10 @@ macro_rules! memory_view {
         ///
         /// [mapping-protocol]: https://docs.python.org/3/c-api/mapping.html
         ///
+        /// The view does not hold a pointer to the memory data: the
+        /// data is fetched again on each access, so the view stays
+        /// valid, and sees the new pages, after the memory has grown.
+        ///
         /// ## Example
         ///
         /// This is an example for the `Uint8Array` view, but it is
@@ -43,10 +52,55 @@ macro_rules! memory_view {
         ///
         /// assert string == 'Hello, World!'
         /// ```
+        ///
+        /// A view can be bounded by a `limit`, i.e. a maximum number
+        /// of elements, to carve a fixed-size window over the memory:
+        ///
+        /// ```py
+        /// from wasmer import Store, Memory, MemoryType
+        ///
+        /// memory = Memory(Store(), MemoryType(1, shared=False))
+        /// window = memory.uint8_view(offset=16, limit=4)
+        ///
+        /// assert len(window) == 4
+        ///
+        /// window[0:4] = [1, 2, 3, 4]
+        ///
+        /// try:
+        ///     window[4]
+        /// except IndexError:
+        ///     pass
+        /// else:
+        ///     assert False
+        /// ```
         #[pyclass]
         pub struct $class_name {
             pub(crate) memory: wasmer::Memory,
             pub(crate) offset: usize,
+            pub(crate) limit: Option<usize>,
+        }
+
+        impl $class_name {
+            /// Returns the absolute end of the view, given the length
+            /// of the whole memory view, i.e. where the limit stops
+            /// the view, if any.
+            fn end(&self, view_length: usize) -> usize {
+                match self.limit {
+                    Some(limit) => min(view_length, self.offset.saturating_add(limit)),
+                    None => view_length,
+                }
+            }
+
+            /// Returns an `IndexError` for an absolute index beyond
+            /// the limit of the view.
+            fn out_of_limit(&self, index: usize) -> PyErr {
+                to_py_err::<PyIndexError, _>(format!(
+                    "Out of bound: Absolute index {} is beyond the view limit (offset {}, limit {})",
+                    index,
+                    self.offset,
+                    self.limit.unwrap_or_default()
+                ))
+            }
         }
 
         #[pymethods]
@@ -62,7 +116,13 @@ macro_rules! memory_view {
         impl PyMappingProtocol for $class_name {
             /// Returns the length of the memory view.
             fn __len__(&self) -> PyResult<usize> {
-                Ok(self.memory.view::<$wasm_type>()[self.offset..].len() / size_of::<$wasm_type>())
+                let length =
+                    self.memory.view::<$wasm_type>()[self.offset..].len() / size_of::<$wasm_type>();
+
+                Ok(match self.limit {
+                    Some(limit) => min(length, limit),
+                    None => length,
+                })
             }
 
             /// Returns one or more values from the memory view.
@@ -86,7 +146,7 @@ macro_rules! memory_view {
                         )));
                     }
 
-                    (offset + slice.start as usize)..(min(offset + slice.stop as usize, view.len()))
+                    (offset + slice.start as usize)..(min(offset + slice.stop as usize, self.end(view.len())))
                 } else if let Ok(index) = index.extract::<isize>() {
                     if index < 0 {
                         return Err(to_py_err::<PyIndexError, _>(
@@ -110,12 +170,18 @@ macro_rules! memory_view {
                     ));
                 };
 
-                if view.len() <= (range.end - 1) {
+                // The maximum index to read, or the first one if the
+                // range has been emptied by the end of the view.
+                let maximum_index = max(range.start, range.end.saturating_sub(1));
+
+                if view.len() <= maximum_index {
                     return Err(to_py_err::<PyIndexError, _>(format!(
                         "Out of bound: Maximum index {} is larger than the memory size {}",
-                        range.end - 1,
+                        maximum_index,
                         view.len()
                     )));
+                } else if self.end(view.len()) <= maximum_index {
+                    return Err(self.out_of_limit(maximum_index));
                 }
 
                 let gil = Python::acquire_gil();
@@ -178,6 +244,13 @@ macro_rules! memory_view {
                         )));
                     }
 
+                    // Check the limit before writing anything.
+                    if let Some((index, _)) = iterator.clone().zip(values.iter()).last() {
+                        if self.end(view.len()) <= offset + index as usize {
+                            return Err(self.out_of_limit(offset + index as usize));
+                        }
+                    }
+
                     for (index, value) in iterator.zip(values.iter()) {
                         let index = index as usize;
                         let value = value.extract::<$wasm_type>()?;
@@ -204,7 +277,9 @@ macro_rules! memory_view {
 
                     let index = index as usize;
 
-                    if view.len() <= offset + index {
+                    if view.len() > offset + index && self.end(view.len()) <= offset + index {
+                        Err(self.out_of_limit(offset + index))
+                    } else if view.len() <= offset + index {
                         Err(to_py_err::<PyIndexError, _>(format!(
                             "Out of bound: Absolute index {} is larger than the memory size {}",
                             offset + index,
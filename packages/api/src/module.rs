@@ -1,10 +1,24 @@
-use crate::{errors::to_py_err, store::Store, types, wasmer_inner::wasmer};
+use crate::{
+    errors::to_py_err,
+    store::{InstanceLimit, Store},
+    types,
+    wasmer_inner::{wasmer, wasmer_types},
+};
 use pyo3::{
-    exceptions::{PyRuntimeError, PyTypeError},
+    exceptions::{PyLookupError, PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyAny, PyBytes, PyList, PyString},
+    types::{PyAny, PyBytes, PyDict, PyList, PyString},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::{TryFrom, TryInto},
+    path::PathBuf,
+    sync::{atomic::AtomicUsize, Arc, Mutex, OnceLock},
 };
-use std::convert::TryInto;
+
+/// The maximum size of an image returned by
+/// `Module.initial_memory_data(image=True)`, i.e. 1024 pages.
+const MAX_IMAGE_SIZE: usize = 64 * 1024 * 1024;
 
 /// A WebAssembly module contains stateless WebAssembly code that has
 /// already been compiled and can be instantiated multiple times.
@@ -33,17 +47,77 @@ use std::convert::TryInto;
 ///
 /// # Let's compile WebAssembly from WAT.
 /// module = Module(store, '(module)')
+///
+/// # Let's compile WebAssembly from a file-like object.
+/// with open('tests/tests.wasm', 'rb') as file:
+///     module = Module(store, file)
+/// ```
+///
+/// A file-like object is any object with a `read` method returning
+/// bytes, e.g. an opened file, a ZIP member, a network stream
+/// etc. The content is read entirely before being compiled.
+///
+/// The optional `names` argument is a dictionary mapping function
+/// indices to names, e.g. a symbol map shipped separately from a
+/// stripped module. The names are used by the trap backtraces, as if
+/// they were in the name section of the module; they take precedence
+/// over the names of the module, if any.
+///
+/// ```py
+/// from wasmer import Store, Module, Instance
+///
+/// module = Module(
+///     Store(),
+///     '(module (func (export "run") unreachable))',
+///     names={0: 'my_run'}
+/// )
+///
+/// try:
+///     Instance(module).exports.run()
+/// except RuntimeError as error:
+///     assert 'at my_run' in str(error)
+/// else:
+///     assert False
 /// ```
 #[pyclass(unsendable)]
-#[text_signature = "(store, bytes)"]
+#[text_signature = "(store, bytes, names)"]
+#[derive(Clone)]
 pub struct Module {
     inner: wasmer::Module,
+    /// Whether the functions of the instances are strictly
+    /// marshaled, as configured by the store.
+    strict_marshaling: bool,
+    /// The live instances of the store, see `Store(max_instances=N)`.
+    instance_limit: Arc<InstanceLimit>,
+    /// The number of calls to `Store.interrupt` of the store, shared
+    /// with the store.
+    interrupts: Arc<AtomicUsize>,
+    /// The bytes the module has been created from, analyzed on first
+    /// use, if known, see `Module.required_features` and
+    /// `ExportType.function_body`.
+    analysis: Option<Arc<LazyAnalysis>>,
+    /// The name given with `Module.name`, if any. It overrides the
+    /// name of the compiled artifact, which is shared by the clones
+    /// of the module, see `Module.clone`.
+    name: Option<String>,
 }
 
 impl Module {
     pub(crate) fn inner(&self) -> &wasmer::Module {
         &self.inner
     }
+
+    pub(crate) fn strict_marshaling(&self) -> bool {
+        self.strict_marshaling
+    }
+
+    pub(crate) fn instance_limit(&self) -> &Arc<InstanceLimit> {
+        &self.instance_limit
+    }
+
+    pub(crate) fn interrupts(&self) -> &Arc<AtomicUsize> {
+        &self.interrupts
+    }
 }
 
 #[pymethods]
@@ -64,30 +138,62 @@ impl Module {
     /// ```
     #[text_signature = "(bytes)"]
     #[staticmethod]
-    fn validate(store: &Store, bytes: &PyAny) -> bool {
-        match bytes.downcast::<PyBytes>() {
-            Ok(bytes) => wasmer::Module::validate(store.inner(), bytes.as_bytes()).is_ok(),
+    fn validate(store: &Store, bytes: &PyAny) -> PyResult<bool> {
+        let store = store.inner()?;
+
+        Ok(match bytes.downcast::<PyBytes>() {
+            Ok(bytes) => wasmer::Module::validate(store, bytes.as_bytes()).is_ok(),
             _ => false,
-        }
+        })
     }
 
     #[new]
-    fn new(store: &Store, bytes: &PyAny) -> PyResult<Self> {
-        let store = store.inner();
+    #[args(names = "None")]
+    fn new(store: &Store, bytes: &PyAny, names: Option<HashMap<u32, String>>) -> PyResult<Self> {
+        let strict_marshaling = store.strict_marshaling();
+        let instance_limit = store.instance_limit();
+        let interrupts = store.interrupts();
+        let store = store.inner()?;
 
         // Read the bytes as if there were real bytes or a WAT string.
-        let module = if let Ok(bytes) = bytes.downcast::<PyBytes>() {
-            wasmer::Module::new(store, bytes.as_bytes())
+        let bytes = if let Ok(bytes) = bytes.downcast::<PyBytes>() {
+            bytes.as_bytes()
         } else if let Ok(string) = bytes.downcast::<PyString>() {
-            wasmer::Module::new(store, string.to_str()?.as_bytes())
+            string.to_str()?.as_bytes()
+        } else if bytes.hasattr("read")? {
+            let content = bytes.call_method0("read")?;
+            let content = content.downcast::<PyBytes>().map_err(|_| {
+                to_py_err::<PyTypeError, _>(format!(
+                    "`Module` expects the `read` method of the file-like object to return bytes, got `{}`",
+                    content.get_type().name().unwrap_or("unknown")
+                ))
+            })?;
+
+            content.as_bytes()
         } else {
             return Err(to_py_err::<PyTypeError, _>(
-                "`Module` accepts Wasm bytes or a WAT string",
+                "`Module` accepts Wasm bytes, a WAT string, or a file-like object",
             ));
         };
 
+        // The names are given to the compiler as an additional name
+        // section, that overrides the names of the module.
+        let named_bytes = match names {
+            Some(names) => Some(with_function_names(
+                &wat::parse_bytes(bytes).map_err(to_py_err::<PyRuntimeError, _>)?,
+                names,
+            )),
+            None => None,
+        };
+
         Ok(Module {
-            inner: module.map_err(to_py_err::<PyRuntimeError, _>)?,
+            inner: wasmer::Module::new(store, named_bytes.as_deref().unwrap_or(bytes))
+                .map_err(to_py_err::<PyRuntimeError, _>)?,
+            strict_marshaling,
+            instance_limit,
+            interrupts,
+            analysis: Some(Arc::new(LazyAnalysis::new(bytes))),
+            name: None,
         })
     }
 
@@ -118,16 +224,88 @@ impl Module {
     /// ```
     #[getter]
     fn name(&self) -> Option<&str> {
-        self.inner.name()
+        self.name.as_deref().or_else(|| self.inner.name())
     }
 
     #[setter(name)]
     fn set_name(&mut self, name: &str) -> PyResult<()> {
+        // The compiled artifact can be renamed only while it is not
+        // shared, e.g. with an instance or a clone.
         self.inner.set_name(name);
+        self.name = Some(name.to_string());
 
         Ok(())
     }
 
+    /// Returns a new module sharing the compiled code of this module,
+    /// without compiling it again, but with its own name: renaming
+    /// one of them does not rename the other one. It is handy to
+    /// label several variants of the same module, e.g. one per
+    /// tenant.
+    ///
+    /// The instances of a module and of its clones behave
+    /// identically, as they run the same compiled code.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(Store(), '(module $shared (func (export "f") (result i32) i32.const 42))')
+    /// clone = module.clone()
+    /// clone.name = 'tenant_a'
+    ///
+    /// assert module.name == 'shared'
+    /// assert clone.name == 'tenant_a'
+    /// assert Instance(clone).exports.f() == Instance(module).exports.f()
+    /// ```
+    #[text_signature = "($self)"]
+    fn clone(&self) -> Self {
+        Clone::clone(self)
+    }
+
+    /// The set of the WebAssembly features that the module uses
+    /// beyond the MVP, e.g. `{"simd", "bulk_memory"}`, or `None` if
+    /// unknown, e.g. for a deserialized module. The names are the
+    /// ones of the Wasmer features: `bulk_memory`, `memory64`,
+    /// `module_linking`, `multi_memory`, `multi_value`,
+    /// `reference_types`, `simd`, `tail_call` and `threads`. It lets
+    /// a host check a module against its policy before instantiating
+    /// it.
+    ///
+    /// A feature is required when the module is invalid without it.
+    /// The features are found on first access, in a single pass over
+    /// the bytes of the module, and then cached.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    ///
+    /// assert Module(store, '(module (func (export "f") (result i32) i32.const 1))').required_features == set()
+    ///
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (func (export "swap") (param i32 i64) (result i64 i32)
+    ///         local.get 1
+    ///         local.get 0))
+    ///     """
+    /// )
+    ///
+    /// assert module.required_features == {'multi_value'}
+    /// ```
+    #[getter]
+    fn required_features(&self) -> Option<HashSet<&'static str>> {
+        self.analysis
+            .as_ref()
+            .and_then(|analysis| analysis.get())
+            .map(|analysis| analysis.required_features.clone())
+    }
+
     /// Returns a list of `ExportType` objects, which represents all
     /// the exports of this module.
     ///
@@ -138,8 +316,84 @@ impl Module {
     ///
     /// See the `ExportType` class to learn more.
     #[getter]
-    fn exports(&self) -> PyResult<Vec<types::ExportType>> {
-        self.inner.exports().map(TryInto::try_into).collect()
+    fn exports(&self, py: Python) -> PyResult<Vec<types::ExportType>> {
+        let info = self.inner.info();
+        let function_bodies = self
+            .analysis
+            .as_ref()
+            .and_then(|analysis| analysis.get())
+            .map(|analysis| &analysis.function_bodies);
+
+        self.inner
+            .exports()
+            .map(|export| {
+                let mut export_type: types::ExportType = export.try_into()?;
+
+                if let (Some(wasmer_types::ExportIndex::Function(index)), Some(function_bodies)) =
+                    (info.exports.get(&export_type.name), function_bodies)
+                {
+                    if let Some(&(local_count, body_size)) = info
+                        .local_func_index(*index)
+                        .and_then(|index| function_bodies.get(index.as_u32() as usize))
+                    {
+                        export_type.function_body =
+                            types::function_body(py, local_count, body_size)?;
+                    }
+                }
+
+                Ok(export_type)
+            })
+            .collect()
+    }
+
+    /// Returns a dictionary mapping each export name to its
+    /// `ExportType`, like `Module.exports` does as a list. It is handy
+    /// to look up the type of a specific export.
+    ///
+    /// The order of the exports is the same as in the WebAssembly
+    /// bytecode. Export names are unique in a valid module; a
+    /// `ValueError` is raised otherwise.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, FunctionType, MemoryType
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add)
+    ///       (memory (export "memory") 1))
+    ///     """
+    /// )
+    /// exports = module.exports_dict()
+    ///
+    /// assert list(exports.keys()) == ['sum', 'memory']
+    /// assert isinstance(exports['sum'].type, FunctionType)
+    /// assert isinstance(exports['memory'].type, MemoryType)
+    /// ```
+    #[text_signature = "($self)"]
+    fn exports_dict<'p>(&self, py: Python<'p>) -> PyResult<&'p PyDict> {
+        let exports_dict = PyDict::new(py);
+
+        for export in self.exports(py)? {
+            let name = export.name.clone();
+
+            if exports_dict.contains(&name)? {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The module has several exports named `{}`",
+                    name
+                )));
+            }
+
+            exports_dict.set_item(name, Py::new(py, export)?)?;
+        }
+
+        Ok(exports_dict)
     }
 
     /// Returns a list of `ImportType` objects, which represents all
@@ -156,6 +410,51 @@ impl Module {
         self.inner.imports().map(TryInto::try_into).collect()
     }
 
+    /// Returns a dictionary mapping each import namespace (also known
+    /// as module name) to the list of `ImportType` objects of this
+    /// namespace. It is handy to build an `ImportObject` namespace by
+    /// namespace.
+    ///
+    /// The order of the namespaces, and of the imports inside a
+    /// namespace, is the same as in the WebAssembly bytecode.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (import "env" "sum" (func (param i32 i32) (result i32)))
+    ///       (import "wasi_snapshot_preview1" "proc_exit" (func (param i32)))
+    ///       (import "env" "memory" (memory 1)))
+    ///     """
+    /// )
+    /// imports = module.imports_by_namespace()
+    ///
+    /// assert list(imports.keys()) == ['env', 'wasi_snapshot_preview1']
+    /// assert [import_.name for import_ in imports['env']] == ['sum', 'memory']
+    /// assert [import_.name for import_ in imports['wasi_snapshot_preview1']] == ['proc_exit']
+    /// ```
+    #[text_signature = "($self)"]
+    fn imports_by_namespace<'p>(&self, py: Python<'p>) -> PyResult<&'p PyDict> {
+        let imports_by_namespace = PyDict::new(py);
+
+        for import in self.inner.imports() {
+            let namespace = import.module().to_string();
+            let import = Py::new(py, types::ImportType::try_from(import)?)?;
+
+            match imports_by_namespace.get_item(&namespace) {
+                Some(imports) => imports.downcast::<PyList>()?.append(import)?,
+                None => imports_by_namespace.set_item(namespace, PyList::new(py, &[import]))?,
+            }
+        }
+
+        Ok(imports_by_namespace)
+    }
+
     /// Get the custom sections of the module given a `name`.
     ///
     /// ## Important
@@ -189,6 +488,404 @@ impl Module {
         )
     }
 
+    /// Parses the standardized `producers` custom section of the
+    /// module, which describes the tools that produced it. It returns
+    /// a dictionary mapping each field (`language`, `processed-by` or
+    /// `sdk`) to a dictionary mapping each tool name to its version,
+    /// or `None` if the module has no `producers` section.
+    ///
+    /// See the [specification of the `producers`
+    /// section](https://github.com/WebAssembly/tool-conventions/blob/master/ProducersSection.md)
+    /// to learn more.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    ///
+    /// # A module with a `producers` custom section.
+    /// module = Module(
+    ///     store,
+    ///     b'\x00asm\x01\x00\x00\x00' +
+    ///     b'\x00\x36\x09producers' +
+    ///     b'\x02' +
+    ///     b'\x08language\x01\x04Rust\x00' +
+    ///     b'\x0cprocessed-by\x01\x05rustc\x061.49.0'
+    /// )
+    ///
+    /// assert module.producers == {
+    ///     'language': {'Rust': ''},
+    ///     'processed-by': {'rustc': '1.49.0'},
+    /// }
+    ///
+    /// # A module without a `producers` custom section.
+    /// assert Module(store, '(module)').producers == None
+    /// ```
+    #[getter]
+    fn producers<'p>(&self, py: Python<'p>) -> PyResult<Option<&'p PyDict>> {
+        let section = match self.inner.custom_sections("producers").next() {
+            Some(section) => section,
+            None => return Ok(None),
+        };
+
+        let producers = PyDict::new(py);
+        let reader = wasmparser::ProducersSectionReader::new(&section, 0)
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        for field in reader {
+            let field = field.map_err(to_py_err::<PyRuntimeError, _>)?;
+            let values = PyDict::new(py);
+
+            for value in field
+                .get_producer_field_values_reader()
+                .map_err(to_py_err::<PyRuntimeError, _>)?
+            {
+                let value = value.map_err(to_py_err::<PyRuntimeError, _>)?;
+
+                values.set_item(value.name, value.version)?;
+            }
+
+            producers.set_item(field.name, values)?;
+        }
+
+        Ok(Some(producers))
+    }
+
+    /// Returns the start function of the module, i.e. the function
+    /// called when the module is instantiated, or `None` if the
+    /// module has no start function.
+    ///
+    /// The start function is described by a dictionary with the
+    /// `index` of the function (in the function index space, where
+    /// imported functions come first), and its `name`: the name from
+    /// the `name` custom section if any, otherwise the name under
+    /// which the function is exported if any, otherwise `None`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "env" "log" (func $log))
+    ///       (func $initialize)
+    ///       (start $initialize))
+    ///     """
+    /// )
+    ///
+    /// assert module.start_function == {'index': 1, 'name': 'initialize'}
+    ///
+    /// # A module without a start function.
+    /// assert Module(store, '(module)').start_function == None
+    /// ```
+    #[getter]
+    fn start_function<'p>(&self, py: Python<'p>) -> PyResult<Option<&'p PyDict>> {
+        let info = self.inner.info();
+        let index = match info.start_function {
+            Some(index) => index,
+            None => return Ok(None),
+        };
+        let name = info.function_names.get(&index).cloned().or_else(|| {
+            info.exports
+                .iter()
+                .find(|(_, export_index)| {
+                    **export_index == wasmer_types::ExportIndex::Function(index)
+                })
+                .map(|(name, _)| name.clone())
+        });
+
+        let start_function = PyDict::new(py);
+        start_function.set_item("index", index.as_u32())?;
+        start_function.set_item("name", name)?;
+
+        Ok(Some(start_function))
+    }
+
+    /// The number of memories of the module, imported and defined
+    /// ones. It is cheaper than counting the memories in
+    /// `Module.imports` and `Module.exports`, and it also counts the
+    /// memories that are not exported.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    ///
+    /// assert Module(store, '(module (memory 1))').memory_count == 1
+    /// assert Module(store, '(module (import "env" "memory" (memory 1)))').memory_count == 1
+    /// assert Module(store, '(module)').memory_count == 0
+    /// ```
+    #[getter]
+    fn memory_count(&self) -> usize {
+        self.inner.info().memories.len()
+    }
+
+    /// The number of tables of the module, imported and defined
+    /// ones. See `Module.memory_count`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    ///
+    /// assert Module(store, '(module (table 1 funcref))').table_count == 1
+    /// assert Module(store, '(module)').table_count == 0
+    /// ```
+    #[getter]
+    fn table_count(&self) -> usize {
+        self.inner.info().tables.len()
+    }
+
+    /// Reads the data segments that initialize the memories of the
+    /// module, without instantiating it. It is handy for a host to
+    /// read the constant data of a module, like an embedded
+    /// configuration.
+    ///
+    /// By default, a list of dictionaries is returned, one per active
+    /// data segment, in the order of the module, with the keys:
+    ///
+    /// * `memory_index`, the index of the memory to initialize,
+    /// * `offset`, the offset of the data in the memory,
+    /// * `global_index`, the index of the (imported) global whose
+    ///   value is added to `offset`, or `None` if the offset is
+    ///   constant,
+    /// * `data`, the data as bytes.
+    ///
+    /// With `image=True`, the initial content of the memory
+    /// `memory_index` (the memory 0 by default) is returned as
+    /// bytes instead: the data segments are written in order onto
+    /// zeroed bytes, of the minimum size of the memory. It can be
+    /// large, and it is only computed on demand. A `ValueError` is
+    /// raised if the minimum size of the memory exceeds 64MiB (1024
+    /// pages), if a data segment depends on the value of a global, or
+    /// if it does not fit in the minimum size of the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (memory 1)
+    ///       (data (i32.const 4) "hello")
+    ///       (data (i32.const 16) "world"))
+    ///     """
+    /// )
+    ///
+    /// assert module.initial_memory_data() == [
+    ///     {'memory_index': 0, 'offset': 4, 'global_index': None, 'data': b'hello'},
+    ///     {'memory_index': 0, 'offset': 16, 'global_index': None, 'data': b'world'},
+    /// ]
+    ///
+    /// image = module.initial_memory_data(image=True)
+    ///
+    /// assert len(image) == 65536
+    /// assert image[0:21] == b'\0\0\0\0hello\0\0\0\0\0\0\0world'
+    /// ```
+    #[text_signature = "($self, image, memory_index)"]
+    #[args(image = "false", memory_index = "0")]
+    fn initial_memory_data(
+        &self,
+        py: Python,
+        image: bool,
+        memory_index: u32,
+    ) -> PyResult<PyObject> {
+        let data_initializers = self.inner.artifact().data_initializers();
+
+        if !image {
+            return data_initializers
+                .iter()
+                .map(|data_initializer| {
+                    let location = &data_initializer.location;
+                    let segment = PyDict::new(py);
+                    segment.set_item("memory_index", location.memory_index.as_u32())?;
+                    segment.set_item("offset", location.offset)?;
+                    segment.set_item(
+                        "global_index",
+                        location.base.map(|global_index| global_index.as_u32()),
+                    )?;
+                    segment.set_item("data", PyBytes::new(py, &data_initializer.data))?;
+
+                    Ok(segment.to_object(py))
+                })
+                .collect::<PyResult<Vec<_>>>()
+                .map(|segments| PyList::new(py, segments).to_object(py));
+        }
+
+        let memory_index = wasmer_types::MemoryIndex::from_u32(memory_index);
+        let memory = self
+            .inner
+            .info()
+            .memories
+            .get(memory_index)
+            .ok_or_else(|| {
+                to_py_err::<PyLookupError, _>(format!(
+                    "The module has no memory at index {}",
+                    memory_index.as_u32()
+                ))
+            })?;
+        let size = memory.minimum.bytes().0;
+
+        if size > MAX_IMAGE_SIZE {
+            return Err(to_py_err::<PyValueError, _>(format!(
+                "The memory has a minimum size of {} byte(s), the image is limited to {} byte(s)",
+                size, MAX_IMAGE_SIZE
+            )));
+        }
+
+        let mut bytes = vec![0; size];
+
+        for data_initializer in data_initializers
+            .iter()
+            .filter(|data_initializer| data_initializer.location.memory_index == memory_index)
+        {
+            let location = &data_initializer.location;
+
+            if let Some(global_index) = location.base {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "A data segment is placed at the value of the global at index {}, the image cannot be computed without instantiating the module",
+                    global_index.as_u32()
+                )));
+            }
+
+            let end = location.offset + data_initializer.data.len();
+
+            if end > bytes.len() {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "A data segment ends at the offset {}, out of the {} byte(s) of the memory",
+                    end,
+                    bytes.len()
+                )));
+            }
+
+            bytes[location.offset..end].copy_from_slice(&data_initializer.data);
+        }
+
+        Ok(PyBytes::new(py, &bytes).to_object(py))
+    }
+
+    /// Generates a Python stub, in the `.pyi` format, that describes
+    /// the exports of the module. It declares an `Exports` class,
+    /// where exported functions are methods with their parameter and
+    /// result types mapped to Python types, and other exports are
+    /// attributes. The stub can be saved alongside the code to help
+    /// IDEs and type checkers with `Instance.exports`.
+    ///
+    /// Exports whose names are not valid Python identifiers are
+    /// listed as comments.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add)
+    ///       (func (export "swap") (param f32 i64) (result i64 f32)
+    ///         local.get 1
+    ///         local.get 0)
+    ///       (func (export "run"))
+    ///       (memory (export "memory") 1))
+    ///     """
+    /// )
+    ///
+    /// assert module.generate_stub() == (
+    ///     'from typing import Any, Tuple\n'
+    ///     '\n'
+    ///     'from wasmer import Global, Memory, Table\n'
+    ///     '\n'
+    ///     '\n'
+    ///     'class Exports:\n'
+    ///     '    def sum(self, arg0: int, arg1: int) -> int: ...\n'
+    ///     '    def swap(self, arg0: float, arg1: int) -> Tuple[int, float]: ...\n'
+    ///     '    def run(self) -> None: ...\n'
+    ///     '    memory: Memory\n'
+    /// )
+    /// ```
+    #[text_signature = "($self)"]
+    fn generate_stub(&self, py: Python) -> PyResult<String> {
+        let keyword = py.import("keyword")?;
+        let mut stub = String::from(
+            "from typing import Any, Tuple\n\nfrom wasmer import Global, Memory, Table\n\n\nclass Exports:\n",
+        );
+        let mut is_empty = true;
+
+        for export in self.inner.exports() {
+            let name = export.name();
+            let is_identifier = PyString::new(py, name)
+                .call_method0("isidentifier")?
+                .is_true()?
+                && !keyword.call1("iskeyword", (name,))?.is_true()?;
+
+            if !is_identifier {
+                stub.push_str(&format!(
+                    "    # `{}` is not a valid Python identifier.\n",
+                    name
+                ));
+
+                continue;
+            }
+
+            is_empty = false;
+
+            match export.ty() {
+                wasmer::ExternType::Function(function_type) => {
+                    let parameters = function_type
+                        .params()
+                        .iter()
+                        .enumerate()
+                        .map(|(nth, ty)| format!(", arg{}: {}", nth, python_type(ty)))
+                        .collect::<String>();
+                    let results = match function_type.results() {
+                        [] => "None".to_string(),
+                        [result] => python_type(result).to_string(),
+                        results => format!(
+                            "Tuple[{}]",
+                            results
+                                .iter()
+                                .map(python_type)
+                                .collect::<Vec<&str>>()
+                                .join(", ")
+                        ),
+                    };
+
+                    stub.push_str(&format!(
+                        "    def {}(self{}) -> {}: ...\n",
+                        name, parameters, results
+                    ));
+                }
+                wasmer::ExternType::Global(_) => stub.push_str(&format!("    {}: Global\n", name)),
+                wasmer::ExternType::Memory(_) => stub.push_str(&format!("    {}: Memory\n", name)),
+                wasmer::ExternType::Table(_) => stub.push_str(&format!("    {}: Table\n", name)),
+            }
+        }
+
+        if is_empty {
+            stub.push_str("    ...\n");
+        }
+
+        Ok(stub)
+    }
+
     /// Serializes a module into a binary representation that the
     /// `Engine` can later process via `Module.deserialize`.
     ///
@@ -256,9 +953,524 @@ impl Module {
     #[text_signature = "($self, bytes)"]
     #[staticmethod]
     fn deserialize(store: &Store, bytes: &PyBytes) -> PyResult<Self> {
-        let module = unsafe { wasmer::Module::deserialize(store.inner(), bytes.as_bytes()) }
+        let bytes = bytes.as_bytes();
+        let module = store.deserialize_cached(bytes, |store| {
+            unsafe { wasmer::Module::deserialize(store, bytes) }
+                .map_err(to_py_err::<PyRuntimeError, _>)
+        })?;
+
+        Ok(Module {
+            inner: module,
+            strict_marshaling: store.strict_marshaling(),
+            instance_limit: store.instance_limit(),
+            interrupts: store.interrupts(),
+            analysis: None,
+            name: None,
+        })
+    }
+
+    /// Serializes the module into the file at `path`, a string, bytes
+    /// or a path-like object such as `pathlib.Path`. The artifact
+    /// can later be loaded with `Module.load_dylib`, which is the
+    /// fastest way to get a module back, e.g. when a server restarts.
+    ///
+    /// With the `Native` engine, the artifact is a shared library
+    /// (`.so`, `.dylib` or `.dll`). The artifact is specific to the
+    /// engine, the platform and the Wasmer version that produced it:
+    /// it must be loaded by the same kind of store on the same
+    /// platform.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    /// import os
+    /// import tempfile
+    ///
+    /// store = Store()
+    /// module = Module(store, '(module (func (export "function")))')
+    ///
+    /// with tempfile.TemporaryDirectory() as directory:
+    ///     path = os.path.join(directory, 'module.artifact')
+    ///     module.serialize_to_file(path)
+    ///
+    ///     module = Module.load_dylib(store, path)
+    ///
+    /// assert module.exports[0].name == 'function'
+    /// ```
+    #[text_signature = "($self, path)"]
+    fn serialize_to_file(&self, py: Python, path: &PyAny) -> PyResult<()> {
+        self.inner
+            .serialize_to_file(to_path(py, path)?)
+            .map_err(to_py_err::<PyRuntimeError, _>)
+    }
+
+    /// Loads a module from the file at `path`, as created by
+    /// `Module.serialize_to_file`. With the `Native` engine, the
+    /// shared library is directly opened (with `dlopen` or similar),
+    /// so nothing is compiled or copied.
+    ///
+    /// A `RuntimeError` is raised if the artifact cannot be loaded,
+    /// e.g. if it has been produced by another engine, for another
+    /// platform, or by another Wasmer version.
+    ///
+    /// ## Safety
+    ///
+    /// Like `Module.deserialize`, this method is inherently
+    /// **unsafe**: the file contains executable code that is loaded
+    /// as is, so it must come from a trusted source.
+    ///
+    /// ## Example
+    ///
+    /// See `Module.serialize_to_file`.
+    #[text_signature = "(store, path)"]
+    #[staticmethod]
+    fn load_dylib(py: Python, store: &Store, path: &PyAny) -> PyResult<Self> {
+        let path = to_path(py, path)?;
+        let module = unsafe { wasmer::Module::deserialize_from_file(store.inner()?, path) }
             .map_err(to_py_err::<PyRuntimeError, _>)?;
 
-        Ok(Module { inner: module })
+        Ok(Module {
+            inner: module,
+            strict_marshaling: store.strict_marshaling(),
+            instance_limit: store.instance_limit(),
+            interrupts: store.interrupts(),
+            analysis: None,
+            name: None,
+        })
+    }
+}
+
+/// Converts a path given as a string, bytes or a path-like object to
+/// a `PathBuf`, like `os.fsdecode` does.
+fn to_path(py: Python, path: &PyAny) -> PyResult<PathBuf> {
+    Ok(PathBuf::from(
+        py.import("os")?
+            .call1("fsdecode", (path,))?
+            .extract::<String>()?,
+    ))
+}
+
+/// Maps a WebAssembly type to the Python type used in generated stubs.
+fn python_type(ty: &wasmer::Type) -> &'static str {
+    match ty {
+        wasmer::Type::I32 | wasmer::Type::I64 | wasmer::Type::V128 => "int",
+        wasmer::Type::F32 | wasmer::Type::F64 => "float",
+        wasmer::Type::ExternRef | wasmer::Type::FuncRef => "Any",
+    }
+}
+
+/// The bytes a module has been created from, analyzed on first use.
+/// The bytes are dropped once analyzed.
+pub(crate) struct LazyAnalysis {
+    bytes: Mutex<Option<Box<[u8]>>>,
+    analysis: OnceLock<Option<Analysis>>,
+}
+
+/// What is learned from the bytes of a module, see `LazyAnalysis`.
+struct Analysis {
+    /// The `(local_count, body_size)` of the functions defined by the
+    /// module, see `ExportType.function_body`.
+    function_bodies: Vec<(u32, usize)>,
+    /// See `Module.required_features`.
+    required_features: HashSet<&'static str>,
+}
+
+impl LazyAnalysis {
+    fn new(bytes: &[u8]) -> Self {
+        Self {
+            bytes: Mutex::new(Some(bytes.into())),
+            analysis: OnceLock::new(),
+        }
+    }
+
+    /// Returns `None` if the bytes cannot be read.
+    fn get(&self) -> Option<&Analysis> {
+        self.analysis
+            .get_or_init(|| {
+                let bytes = self
+                    .bytes
+                    .lock()
+                    .ok()
+                    .and_then(|mut bytes| bytes.take())
+                    .unwrap_or_default();
+
+                analyze(&bytes)
+            })
+            .as_ref()
+    }
+}
+
+/// Reads the module from its bytes or its WAT text, in a single pass
+/// over its sections and operators. It finds:
+///
+/// * the `(local_count, body_size)` of the functions defined by the
+///   module,
+/// * the WebAssembly features beyond the MVP used by the module: a
+///   feature is used if the module is invalid without it.
+///
+/// It returns `None` if the bytes cannot be read.
+fn analyze(bytes: &[u8]) -> Option<Analysis> {
+    use wasmparser::{ElementKind, ImportSectionEntryType, Payload, TypeDef};
+
+    let bytes = wat::parse_bytes(bytes).ok()?;
+    let mut features = HashSet::new();
+    let mut function_bodies = Vec::new();
+    let mut tables = 0usize;
+    let mut memories = 0usize;
+
+    // Reads the operators of a function body or of an initializer
+    // expression. The opcode of an operator tells whether it is a
+    // SIMD (`0xfd`) or an atomic (`0xfe`) one.
+    let read_operators =
+        |operators: wasmparser::OperatorsReader, features: &mut HashSet<_>| -> Option<()> {
+            for operator in operators.into_iter_with_offsets() {
+                let (operator, offset) = operator.ok()?;
+                features.extend(operator_feature(&operator, bytes.get(offset).copied()));
+            }
+
+            Some(())
+        };
+
+    for payload in wasmparser::Parser::new(0).parse_all(&bytes) {
+        match payload.ok()? {
+            Payload::TypeSection(reader) => {
+                for ty in reader {
+                    match ty.ok()? {
+                        TypeDef::Func(ty) => {
+                            if ty.returns.len() > 1 {
+                                features.insert("multi_value");
+                            }
+
+                            features.extend(
+                                ty.params
+                                    .iter()
+                                    .chain(ty.returns.iter())
+                                    .filter_map(value_type_feature),
+                            );
+                        }
+                        TypeDef::Instance(_) | TypeDef::Module(_) => {
+                            features.insert("module_linking");
+                        }
+                    }
+                }
+            }
+
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.ok()?;
+
+                    if import.field.is_none() {
+                        features.insert("module_linking");
+                    }
+
+                    match import.ty {
+                        ImportSectionEntryType::Table(ty) => {
+                            tables += 1;
+                            features.extend(value_type_feature(&ty.element_type));
+                        }
+                        ImportSectionEntryType::Memory(ty) => {
+                            memories += 1;
+                            features.extend(memory_type_features(&ty));
+                        }
+                        ImportSectionEntryType::Global(ty) => {
+                            features.extend(value_type_feature(&ty.content_type));
+                        }
+                        ImportSectionEntryType::Module(_) | ImportSectionEntryType::Instance(_) => {
+                            features.insert("module_linking");
+                        }
+                        ImportSectionEntryType::Function(_) | ImportSectionEntryType::Event(_) => {}
+                    }
+                }
+            }
+
+            Payload::AliasSection(_)
+            | Payload::InstanceSection(_)
+            | Payload::ModuleSectionStart { .. } => {
+                features.insert("module_linking");
+            }
+
+            Payload::TableSection(reader) => {
+                for ty in reader {
+                    tables += 1;
+                    features.extend(value_type_feature(&ty.ok()?.element_type));
+                }
+            }
+
+            Payload::MemorySection(reader) => {
+                for ty in reader {
+                    memories += 1;
+                    features.extend(memory_type_features(&ty.ok()?));
+                }
+            }
+
+            Payload::GlobalSection(reader) => {
+                for global in reader {
+                    let global = global.ok()?;
+
+                    features.extend(value_type_feature(&global.ty.content_type));
+                    read_operators(global.init_expr.get_operators_reader(), &mut features)?;
+                }
+            }
+
+            Payload::ElementSection(reader) => {
+                for element in reader {
+                    let element = element.ok()?;
+
+                    features.extend(value_type_feature(&element.ty));
+
+                    match element.kind {
+                        ElementKind::Active { init_expr, .. } => {
+                            read_operators(init_expr.get_operators_reader(), &mut features)?;
+                        }
+                        ElementKind::Passive | ElementKind::Declared => {
+                            features.insert("bulk_memory");
+                        }
+                    }
+                }
+            }
+
+            Payload::DataCountSection { .. } => {
+                features.insert("bulk_memory");
+            }
+
+            Payload::DataSection(reader) => {
+                for data in reader {
+                    match data.ok()?.kind {
+                        wasmparser::DataKind::Active { init_expr, .. } => {
+                            read_operators(init_expr.get_operators_reader(), &mut features)?;
+                        }
+                        wasmparser::DataKind::Passive => {
+                            features.insert("bulk_memory");
+                        }
+                    }
+                }
+            }
+
+            Payload::CodeSectionEntry(body) => {
+                let mut locals = body.get_locals_reader().ok()?;
+                let mut local_count = 0u32;
+
+                for _ in 0..locals.get_count() {
+                    let (count, ty) = locals.read().ok()?;
+                    local_count = local_count.saturating_add(count);
+                    features.extend(value_type_feature(&ty));
+                }
+
+                let range = body.range();
+                function_bodies.push((local_count, range.end - range.start));
+
+                read_operators(body.get_operators_reader().ok()?, &mut features)?;
+            }
+
+            _ => {}
+        }
+    }
+
+    if tables > 1 {
+        features.insert("reference_types");
     }
+
+    if memories > 1 {
+        features.insert("multi_memory");
+    }
+
+    Some(Analysis {
+        function_bodies,
+        required_features: features,
+    })
+}
+
+/// The feature required by a value type, if any.
+fn value_type_feature(ty: &wasmparser::Type) -> Option<&'static str> {
+    match ty {
+        wasmparser::Type::V128 => Some("simd"),
+        wasmparser::Type::FuncRef | wasmparser::Type::ExternRef => Some("reference_types"),
+        _ => None,
+    }
+}
+
+/// The features required by a memory type.
+fn memory_type_features(ty: &wasmparser::MemoryType) -> Vec<&'static str> {
+    let (memory64, shared) = match ty {
+        wasmparser::MemoryType::M32 { shared, .. } => (false, *shared),
+        wasmparser::MemoryType::M64 { shared, .. } => (true, *shared),
+    };
+
+    [(memory64, "memory64"), (shared, "threads")]
+        .iter()
+        .filter(|(required, _)| *required)
+        .map(|(_, feature)| *feature)
+        .collect()
+}
+
+/// The feature required by an operator, if any, given its opcode.
+fn operator_feature(operator: &wasmparser::Operator, opcode: Option<u8>) -> Option<&'static str> {
+    use wasmparser::{Operator::*, TypeOrFuncType};
+
+    Some(match operator {
+        Block {
+            ty: TypeOrFuncType::FuncType(_),
+        }
+        | Loop {
+            ty: TypeOrFuncType::FuncType(_),
+        }
+        | If {
+            ty: TypeOrFuncType::FuncType(_),
+        } => "multi_value",
+        ReturnCall { .. } | ReturnCallIndirect { .. } => "tail_call",
+        TypedSelect { .. }
+        | RefNull { .. }
+        | RefIsNull
+        | RefFunc { .. }
+        | TableGet { .. }
+        | TableSet { .. }
+        | TableGrow { .. }
+        | TableSize { .. }
+        | TableFill { .. } => "reference_types",
+        MemoryInit { .. }
+        | DataDrop { .. }
+        | MemoryCopy { .. }
+        | MemoryFill { .. }
+        | TableInit { .. }
+        | ElemDrop { .. }
+        | TableCopy { .. } => "bulk_memory",
+        _ => match opcode {
+            Some(0xfd) => "simd",
+            Some(0xfe) => "threads",
+            _ => return None,
+        },
+    })
+}
+
+/// Rewrites the WebAssembly bytes so that the `old_namespace` /
+/// `old_name` import is renamed `new_namespace` / `new_name`, see
+/// `wasmer.rename_import`. All the other bytes are copied as is.
+pub fn rename_import<'py>(
+    py: Python<'py>,
+    bytes: &PyBytes,
+    (old_namespace, old_name): (&str, &str),
+    (new_namespace, new_name): (&str, &str),
+) -> PyResult<&'py PyBytes> {
+    let bytes = bytes.as_bytes();
+    let invalid = |error: wasmparser::BinaryReaderError| {
+        to_py_err::<PyRuntimeError, _>(format!("Invalid WebAssembly bytes: {}", error))
+    };
+
+    let mut reader = wasmparser::BinaryReader::new(bytes);
+    let mut renamed_bytes = reader.read_bytes(8).map_err(invalid)?.to_vec();
+    let mut renamed = false;
+
+    while !reader.eof() {
+        let section_id = reader.read_u8().map_err(invalid)?;
+        let section_size = reader.read_var_u32().map_err(invalid)?;
+        let section = reader.read_bytes(section_size as usize).map_err(invalid)?;
+
+        // Only the import section (whose identifier is 2) is
+        // rewritten.
+        if section_id != 2 {
+            renamed_bytes.push(section_id as u8);
+            write_var_u32(&mut renamed_bytes, section_size);
+            renamed_bytes.extend_from_slice(section);
+
+            continue;
+        }
+
+        let mut imports = wasmparser::ImportSectionReader::new(section, 0).map_err(invalid)?;
+        let mut renamed_section = Vec::with_capacity(section.len());
+        write_var_u32(&mut renamed_section, imports.get_count());
+
+        for _ in 0..imports.get_count() {
+            let start = imports.original_position();
+            let import = imports.read().map_err(invalid)?;
+            let end = imports.original_position();
+
+            // The import descriptor follows the namespace and the
+            // name, that are the only parts to rewrite.
+            let mut entry = wasmparser::BinaryReader::new(&section[start..end]);
+            entry.read_string().map_err(invalid)?;
+            entry.read_string().map_err(invalid)?;
+
+            if import.field.is_none() {
+                entry.read_u8().map_err(invalid)?;
+            }
+
+            let descriptor = &section[start + entry.current_position()..end];
+
+            if import.module == old_namespace && import.field == Some(old_name) {
+                write_string(&mut renamed_section, new_namespace);
+                write_string(&mut renamed_section, new_name);
+                renamed_section.extend_from_slice(descriptor);
+                renamed = true;
+            } else {
+                renamed_section.extend_from_slice(&section[start..end]);
+            }
+        }
+
+        renamed_bytes.push(section_id as u8);
+        write_var_u32(&mut renamed_bytes, renamed_section.len() as u32);
+        renamed_bytes.extend_from_slice(&renamed_section);
+    }
+
+    if !renamed {
+        return Err(to_py_err::<PyLookupError, _>(format!(
+            "The module has no import named `{}` in the `{}` namespace",
+            old_name, old_namespace
+        )));
+    }
+
+    Ok(PyBytes::new(py, &renamed_bytes))
+}
+
+/// Appends a name section with the given function names to the
+/// WebAssembly bytes, see `Module(…, names=…)`.
+fn with_function_names(bytes: &[u8], names: HashMap<u32, String>) -> Vec<u8> {
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    names.sort();
+
+    let mut function_names = Vec::new();
+    write_var_u32(&mut function_names, names.len() as u32);
+
+    for (index, name) in names {
+        write_var_u32(&mut function_names, index);
+        write_string(&mut function_names, &name);
+    }
+
+    let mut section = Vec::new();
+    write_string(&mut section, "name");
+    // The function names subsection has the identifier 1.
+    section.push(1);
+    write_var_u32(&mut section, function_names.len() as u32);
+    section.extend_from_slice(&function_names);
+
+    let mut named_bytes = bytes.to_vec();
+    // The custom sections have the identifier 0.
+    named_bytes.push(0);
+    write_var_u32(&mut named_bytes, section.len() as u32);
+    named_bytes.extend_from_slice(&section);
+
+    named_bytes
+}
+
+/// Writes `value` as an unsigned LEB128 integer.
+fn write_var_u32(bytes: &mut Vec<u8>, mut value: u32) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+
+            break;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Writes `string` as a WebAssembly name, i.e. its length followed
+/// by its UTF-8 bytes.
+fn write_string(bytes: &mut Vec<u8>, string: &str) {
+    write_var_u32(bytes, string.len() as u32);
+    bytes.extend_from_slice(string.as_bytes());
 }
@@ -1,8 +1,21 @@
 use crate::{
     errors::to_py_err,
+    memory,
     wasmer_inner::{wasmer, wasmer_engines as engines},
 };
-use pyo3::{exceptions::PyTypeError, prelude::*};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyTypeError},
+    prelude::*,
+};
+use std::{
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 /// The store represents all global state that can be manipulated by
 /// WebAssembly programs. It consists of the runtime representation of
@@ -43,31 +56,281 @@ use pyo3::{exceptions::PyTypeError, prelude::*};
 /// `compiler_compiler_cranelift`, `compiler_compiler_llvm`,
 /// `compiler_compiler_singlepass`, otherwise it will run in headless
 /// mode.
+///
+/// An `int` and a `float` are never converted into each other when
+/// passed to or returned by a function. However, by default, a
+/// `bool` is accepted as an integer, since it is an `int` in Python;
+/// a `decimal.Decimal` or a `fractions.Fraction` is converted with
+/// `float()` when a float is expected; and a NumPy scalar is
+/// converted to its Python scalar. With `strict_marshaling=True`,
+/// these conversions are refused as well, both for the arguments
+/// passed to the exported functions of the instances created from
+/// the store and for the results returned by the host functions
+/// created with the store: a `TypeError` is raised instead, naming
+/// the argument or the result.
+///
+/// ```py
+/// from wasmer import Store, Module, Instance
+///
+/// store = Store(strict_marshaling=True)
+/// module = Module(
+///     store,
+///     """
+///     (module
+///       (func (export "negate") (param i32) (result i32)
+///         i32.const 0
+///         local.get 0
+///         i32.sub))
+///     """
+/// )
+/// negate = Instance(module).exports.negate
+///
+/// assert negate(1) == -1
+///
+/// try:
+///     negate(True)
+/// except TypeError:
+///     pass
+/// else:
+///     assert False
+/// ```
+///
+/// With `cache_size=N`, the store keeps the `N` modules most
+/// recently deserialized by `Module.deserialize`, along with their
+/// artifact. Deserializing the same artifact again returns the
+/// cached module instead of deserializing it again. The cache is
+/// disabled by default, and can be emptied with
+/// `Store.clear_module_cache`.
+///
+/// ```py
+/// from wasmer import Store, Module
+///
+/// store = Store(cache_size=8)
+/// artifact = Module(store, '(module)').serialize()
+///
+/// module = Module.deserialize(store, artifact)
+/// module = Module.deserialize(store, artifact) # Cached!
+///
+/// assert store.cached_module_count == 1
+///
+/// store.clear_module_cache()
+///
+/// assert store.cached_module_count == 0
+/// ```
+///
+/// With `max_instances=N`, at most `N` instances created from the
+/// modules of the store can be alive at the same time: a
+/// `RuntimeError` is raised when instantiating one more. An instance
+/// stops counting once it is closed with `Instance.close`, or
+/// garbage collected. There is no limit by default.
+///
+/// ```py
+/// from wasmer import Store, Module, Instance
+///
+/// store = Store(max_instances=1)
+/// module = Module(store, '(module)')
+/// instance = Instance(module)
+///
+/// assert store.live_instance_count == 1
+///
+/// try:
+///     Instance(module)
+/// except RuntimeError:
+///     pass
+/// else:
+///     assert False
+///
+/// instance.close()
+/// instance = Instance(module)
+/// ```
+///
+/// A store can be used as a context manager. Its engine resources
+/// are released when exiting the `with` block, and the store cannot
+/// be used anymore:
+///
+/// ```py
+/// from wasmer import Store, Module
+///
+/// with Store() as store:
+///     module = Module(store, '(module)')
+///
+/// try:
+///     Module(store, '(module)')
+/// except RuntimeError:
+///     pass
+/// else:
+///     assert False
+/// ```
 #[pyclass]
-#[text_signature = "(engine)"]
+#[text_signature = "(engine, strict_marshaling, cache_size, max_instances)"]
 pub struct Store {
-    inner: wasmer::Store,
+    /// The store, or `None` once it has been closed with
+    /// `Store.close` or by exiting a `with` block.
+    inner: Option<wasmer::Store>,
     engine_name: String,
     compiler_name: Option<String>,
+    strict_marshaling: bool,
+    /// The most recently deserialized modules, the most recent last,
+    /// keyed by the hash of their artifact, and stored along with the
+    /// artifact itself so that a hash collision cannot return the
+    /// wrong module.
+    module_cache: RefCell<Vec<CachedModule>>,
+    cache_size: usize,
+    instance_limit: Arc<InstanceLimit>,
+    /// The number of calls to `Store.interrupt`, shared with the
+    /// functions of the instances of the store.
+    interrupts: Arc<AtomicUsize>,
+}
+
+/// Counts the live instances created from the modules of a store,
+/// see `Store(max_instances=N)`.
+pub(crate) struct InstanceLimit {
+    max_instances: Option<usize>,
+    live_instances: AtomicUsize,
+}
+
+impl InstanceLimit {
+    /// Counts one more live instance, or raises a `RuntimeError` if
+    /// the limit is reached. The instance stops counting when the
+    /// returned slot is dropped.
+    pub(crate) fn acquire(self: &Arc<Self>) -> PyResult<InstanceSlot> {
+        let live_instances = self.live_instances.fetch_add(1, Ordering::SeqCst);
+
+        if let Some(max_instances) = self.max_instances {
+            if live_instances >= max_instances {
+                self.live_instances.fetch_sub(1, Ordering::SeqCst);
+
+                return Err(to_py_err::<PyRuntimeError, _>(format!(
+                    "The store cannot have more than {} live instance(s); close some with `Instance.close`",
+                    max_instances
+                )));
+            }
+        }
+
+        Ok(InstanceSlot(self.clone()))
+    }
+}
+
+/// A live instance counted by an `InstanceLimit`.
+pub(crate) struct InstanceSlot(Arc<InstanceLimit>);
+
+impl Drop for InstanceSlot {
+    fn drop(&mut self) {
+        self.0.live_instances.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A module of the module cache of a store, see `Store.cache_size`.
+/// It is found by the hash of its serialized artifact, which is kept
+/// to tell a hash collision apart.
+struct CachedModule {
+    hash: u64,
+    artifact: Box<[u8]>,
+    module: wasmer::Module,
 }
 
 impl Store {
-    pub fn inner(&self) -> &wasmer::Store {
-        &self.inner
+    pub fn inner(&self) -> PyResult<&wasmer::Store> {
+        self.inner.as_ref().ok_or_else(|| {
+            to_py_err::<PyRuntimeError, _>(
+                "The store is closed, it cannot be used after `Store.close` or exiting its `with` block",
+            )
+        })
+    }
+
+    /// Creates a Wasmer store whose memories refuse to grow while one
+    /// of their buffers is exported, see `memory::Tunables`.
+    fn new_store<E>(engine: &E) -> wasmer::Store
+    where
+        E: wasmer::Engine,
+    {
+        wasmer::Store::new_with_tunables(engine, memory::Tunables::for_target(engine.target()))
+    }
+
+    pub(crate) fn strict_marshaling(&self) -> bool {
+        self.strict_marshaling
+    }
+
+    pub(crate) fn instance_limit(&self) -> Arc<InstanceLimit> {
+        self.instance_limit.clone()
+    }
+
+    pub(crate) fn interrupts(&self) -> Arc<AtomicUsize> {
+        self.interrupts.clone()
+    }
+
+    /// Returns the module deserialized from `artifact`, from the
+    /// module cache if any, otherwise with `deserialize`.
+    pub(crate) fn deserialize_cached<F>(
+        &self,
+        artifact: &[u8],
+        deserialize: F,
+    ) -> PyResult<wasmer::Module>
+    where
+        F: FnOnce(&wasmer::Store) -> PyResult<wasmer::Module>,
+    {
+        let store = self.inner()?;
+
+        if self.cache_size == 0 {
+            return deserialize(store);
+        }
+
+        let mut hasher = DefaultHasher::new();
+        artifact.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let mut module_cache = self.module_cache.borrow_mut();
+
+        if let Some(position) = module_cache
+            .iter()
+            .position(|entry| entry.hash == hash && *entry.artifact == *artifact)
+        {
+            let entry = module_cache.remove(position);
+            let module = entry.module.clone();
+            module_cache.push(entry);
+
+            return Ok(module);
+        }
+
+        let module = deserialize(store)?;
+
+        if module_cache.len() == self.cache_size {
+            module_cache.remove(0);
+        }
+
+        module_cache.push(CachedModule {
+            hash,
+            artifact: artifact.into(),
+            module: module.clone(),
+        });
+
+        Ok(module)
     }
 }
 
 #[pymethods]
 impl Store {
     #[new]
-    fn new(py: Python, engine: Option<&PyAny>) -> PyResult<Self> {
+    #[args(
+        engine = "None",
+        strict_marshaling = "false",
+        cache_size = "0",
+        max_instances = "None"
+    )]
+    fn new(
+        py: Python,
+        engine: Option<&PyAny>,
+        strict_marshaling: bool,
+        cache_size: usize,
+        max_instances: Option<usize>,
+    ) -> PyResult<Self> {
         let (inner, engine_name, compiler_name) = match engine {
             Some(engine) => {
                 if let Ok(jit) = engine.downcast::<PyCell<engines::JIT>>() {
                     let jit = jit.borrow();
 
                     (
-                        wasmer::Store::new(jit.inner()),
+                        Self::new_store(jit.inner()),
                         engines::JIT::name(),
                         jit.compiler_name().cloned(),
                     )
@@ -75,7 +338,7 @@ impl Store {
                     let native = native.borrow();
 
                     (
-                        wasmer::Store::new(native.inner()),
+                        Self::new_store(native.inner()),
                         engines::Native::name(),
                         native.compiler_name().cloned(),
                     )
@@ -99,10 +362,10 @@ impl Store {
                     .ok();
 
                 let target = None;
-                let engine = engines::JIT::raw_new(compiler, target)?;
+                let engine = engines::JIT::raw_new(compiler, target, false)?;
 
                 (
-                    wasmer::Store::new(engine.inner()),
+                    Self::new_store(engine.inner()),
                     engines::JIT::name(),
                     engine.compiler_name().cloned(),
                 )
@@ -110,12 +373,99 @@ impl Store {
         };
 
         Ok(Self {
-            inner,
+            inner: Some(inner),
             engine_name: engine_name.to_string(),
             compiler_name,
+            strict_marshaling,
+            module_cache: RefCell::new(Vec::new()),
+            cache_size,
+            instance_limit: Arc::new(InstanceLimit {
+                max_instances,
+                live_instances: AtomicUsize::new(0),
+            }),
+            interrupts: Arc::new(AtomicUsize::new(0)),
         })
     }
 
+    /// Creates a store with a sensible engine and compiler, so that
+    /// there is nothing to choose. It is the same as `Store()`.
+    ///
+    /// The JIT engine is used, with the first compiler package found
+    /// in this order:
+    ///
+    /// 1. `wasmer_compiler_cranelift`,
+    /// 2. `wasmer_compiler_llvm`,
+    /// 3. `wasmer_compiler_singlepass`.
+    ///
+    /// If no compiler package is installed, the engine runs in
+    /// headless mode, i.e. it can only load already compiled modules
+    /// (see `Module.deserialize`).
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store
+    ///
+    /// store = Store.default()
+    ///
+    /// assert store.engine_name == 'jit'
+    /// ```
+    #[staticmethod]
+    #[text_signature = "()"]
+    fn default(py: Python) -> PyResult<Self> {
+        Self::new(py, None, false, 0, None)
+    }
+
+    #[text_signature = "($self)"]
+    fn __enter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    /// Closes the store, see `Store.close`.
+    #[text_signature = "($self, exc_type, exc_value, traceback)"]
+    fn __exit__(&mut self, _exc_type: &PyAny, _exc_value: &PyAny, _traceback: &PyAny) -> bool {
+        self.close();
+
+        false
+    }
+
+    /// Closes the store: it drops its reference to the engine, and
+    /// the modules of its module cache (see `Store.cache_size`), and
+    /// the store cannot be used anymore. Objects built from the
+    /// store, like modules or instances, keep working: they hold
+    /// their own references to the engine, so the engine is only
+    /// freed once they are dropped too. It is the same as exiting a
+    /// `with` block, for code that manages the lifetime of the store
+    /// manually.
+    ///
+    /// It returns `True` the first time, and `False` if the store is
+    /// already closed, so it is safe to call it several times.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    /// module = Module(store, '(module)')
+    ///
+    /// assert store.close() == True
+    /// assert store.close() == False
+    ///
+    /// try:
+    ///     Module(store, '(module)')
+    /// except RuntimeError:
+    ///     pass
+    /// else:
+    ///     assert False
+    /// ```
+    #[text_signature = "($self)"]
+    fn close(&mut self) -> bool {
+        self.module_cache.borrow_mut().clear();
+
+        self.inner.take().is_some()
+    }
+
     #[getter]
     fn engine_name(&self) -> &String {
         &self.engine_name
@@ -125,4 +475,96 @@ impl Store {
     fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
+
+    /// Checks whether the values passed to and returned by functions
+    /// are strictly marshaled, see `Store(strict_marshaling=True)`.
+    #[getter(strict_marshaling)]
+    fn get_strict_marshaling(&self) -> bool {
+        self.strict_marshaling
+    }
+
+    /// Empties the module cache, see `Store(cache_size=N)`.
+    #[text_signature = "($self)"]
+    fn clear_module_cache(&self) {
+        self.module_cache.borrow_mut().clear();
+    }
+
+    /// The number of modules in the module cache, see
+    /// `Store(cache_size=N)`.
+    #[getter]
+    fn cached_module_count(&self) -> usize {
+        self.module_cache.borrow().len()
+    }
+
+    /// The number of live instances created from the modules of the
+    /// store, see `Store(max_instances=N)`.
+    #[getter]
+    fn live_instance_count(&self) -> usize {
+        self.instance_limit.live_instances.load(Ordering::SeqCst)
+    }
+
+    /// Interrupts all the calls in progress to the functions of the
+    /// instances of the store at their next call to a host function:
+    /// the host function is not called, and the call raises a
+    /// `CallInterruptedError` (an `InterruptedError`) instead. The
+    /// calls made afterwards are not interrupted. It is meant to be
+    /// called from another thread, e.g. to abort the WebAssembly code
+    /// on shutdown or when a request is cancelled.
+    ///
+    /// Wasmer cannot interrupt running WebAssembly code, so the
+    /// calls from the WebAssembly code to host functions are the only
+    /// places where a call can be interrupted. A loop that does not
+    /// call the host cannot be interrupted, and a call that completes
+    /// without calling the host again returns its results as usual.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// import threading
+    /// from wasmer import Store, CallInterruptedError
+    ///
+    /// # Only the store can be shared between threads, the module
+    /// # and the instance are created by the thread running the call.
+    /// def run(store, started, errors):
+    ///     from wasmer import Module, Instance, ImportObject, Function
+    ///
+    ///     module = Module(
+    ///         store,
+    ///         """
+    ///         (module
+    ///           (import "env" "tick" (func $tick))
+    ///           (func (export "run")
+    ///             (loop $forever
+    ///               call $tick
+    ///               br $forever)))
+    ///         """
+    ///     )
+    ///
+    ///     def tick():
+    ///         started.set()
+    ///
+    ///     import_object = ImportObject()
+    ///     import_object.register("env", {"tick": Function(store, tick)})
+    ///
+    ///     try:
+    ///         Instance(module, import_object).exports.run()
+    ///     except Exception as error:
+    ///         errors.append(error)
+    ///
+    /// store = Store()
+    /// started = threading.Event()
+    /// errors = []
+    /// thread = threading.Thread(target=run, args=(store, started, errors))
+    /// thread.start()
+    /// started.wait()
+    ///
+    /// store.interrupt()
+    /// thread.join()
+    ///
+    /// assert isinstance(errors[0], CallInterruptedError)
+    /// ```
+    #[text_signature = "($self)"]
+    fn interrupt(&self) {
+        self.interrupts.fetch_add(1, Ordering::SeqCst);
+    }
 }
@@ -3,6 +3,7 @@ use pyo3::{
     class::basic::PyObjectProtocol,
     conversion::{FromPyObject, IntoPy},
     exceptions::PyValueError,
+    once_cell::GILOnceCell,
     prelude::*,
 };
 use std::{convert::TryFrom, slice};
@@ -33,6 +34,29 @@ impl Type {
 
         VARIANTS.iter()
     }
+
+    /// Checks whether the type is a number type, i.e. `I32`, `I64`,
+    /// `F32`, `F64` or `V128`.
+    pub fn is_number(&self) -> bool {
+        !self.is_reference()
+    }
+
+    /// Checks whether the type is a reference type, i.e. `EXTERN_REF`
+    /// or `FUNC_REF`.
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Type::ExternRef | Type::FuncRef)
+    }
+
+    /// Returns the size in bytes of a value of a number type, or
+    /// `None` for a reference type, which has no fixed size.
+    pub fn byte_size(&self) -> Option<usize> {
+        match self {
+            Type::I32 | Type::F32 => Some(4),
+            Type::I64 | Type::F64 => Some(8),
+            Type::V128 => Some(16),
+            Type::ExternRef | Type::FuncRef => None,
+        }
+    }
 }
 
 impl From<&Type> for &'static str {
@@ -49,9 +73,19 @@ impl From<&Type> for &'static str {
     }
 }
 
+/// The Python `Type` enum, set when the `wasmer` module is created,
+/// so that a `Type` is converted into its enum member, e.g.
+/// `Type.I32`, rather than a plain `int`.
+pub(crate) static TYPE_ENUM: GILOnceCell<PyObject> = GILOnceCell::new();
+
 impl ToPyObject for Type {
     fn to_object(&self, py: Python) -> PyObject {
-        (*self as u8).into_py(py)
+        let value = *self as u8;
+
+        TYPE_ENUM
+            .get(py)
+            .and_then(|type_enum| type_enum.call1(py, (value,)).ok())
+            .unwrap_or_else(|| value.into_py(py))
     }
 }
 
@@ -144,6 +178,68 @@ impl FunctionType {
     fn new(params: Vec<Type>, results: Vec<Type>) -> Self {
         Self { params, results }
     }
+
+    /// The number of parameters of the function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import FunctionType, Type
+    ///
+    /// function_type = FunctionType([Type.I32, Type.I64], [Type.F32])
+    ///
+    /// assert function_type.arity == 2
+    /// ```
+    #[getter]
+    fn arity(&self) -> usize {
+        self.params.len()
+    }
+
+    /// The number of results of the function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import FunctionType, Type
+    ///
+    /// function_type = FunctionType([Type.I32, Type.I64], [Type.F32])
+    ///
+    /// assert function_type.result_arity == 1
+    /// ```
+    #[getter]
+    fn result_arity(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Checks whether the function has no parameter.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import FunctionType, Type
+    ///
+    /// assert FunctionType([], [Type.I32]).is_nullary == True
+    /// assert FunctionType([Type.I32], [Type.I32]).is_nullary == False
+    /// ```
+    #[getter]
+    fn is_nullary(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Checks whether the function has no result.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import FunctionType, Type
+    ///
+    /// assert FunctionType([Type.I32], []).is_void == True
+    /// assert FunctionType([Type.I32], [Type.I32]).is_void == False
+    /// ```
+    #[getter]
+    fn is_void(&self) -> bool {
+        self.results.is_empty()
+    }
 }
 
 impl From<&wasmer::FunctionType> for FunctionType {
@@ -279,6 +375,24 @@ impl GlobalType {
     fn new(r#type: Type, mutable: bool) -> Self {
         Self { r#type, mutable }
     }
+
+    /// The type of the value stored in the global. It is an alias of
+    /// `GlobalType.type`, which reads better from a `Global`:
+    /// `global_.type.content_type`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import GlobalType, Type
+    ///
+    /// global_type = GlobalType(Type.I64, mutable=False)
+    ///
+    /// assert global_type.content_type == Type.I64
+    /// ```
+    #[getter]
+    fn content_type(&self) -> Type {
+        self.r#type
+    }
 }
 
 impl From<&wasmer::GlobalType> for GlobalType {
@@ -414,6 +528,33 @@ impl PyObjectProtocol for TableType {
 /// assert exports[3].type.maximum == None
 /// assert exports[3].type.shared == False
 /// ```
+///
+/// For static analysis, an exported function also describes its
+/// body with a `FunctionBody(local_count, body_size)` named tuple:
+/// the number of local variables (parameters excluded), and the size
+/// of the code body in bytes.
+///
+/// ```py
+/// from wasmer import Store, Module
+///
+/// module = Module(
+///     Store(),
+///     """
+///     (module
+///       (func (export "function") (param i32) (result i32)
+///         (local i32 i64 i64)
+///         local.get 0))
+///     """
+/// )
+/// function_body = module.exports[0].function_body
+///
+/// assert function_body.local_count == 3
+/// assert function_body.body_size == 8
+/// ```
+///
+/// It is `None` for other kinds of exports, and when the body is not
+/// known, e.g. for a module built with `Module.deserialize` or for a
+/// re-exported imported function.
 #[pyclass]
 #[text_signature = "(name, type)"]
 pub struct ExportType {
@@ -425,13 +566,22 @@ pub struct ExportType {
     /// `GlobalType`, `TableType` and `MemoryType`.
     #[pyo3(get)]
     pub r#type: PyObject,
+
+    /// The body of an exported function, as a `FunctionBody`, if
+    /// known, otherwise `None`.
+    #[pyo3(get)]
+    pub function_body: PyObject,
 }
 
 #[pymethods]
 impl ExportType {
     #[new]
-    fn new(name: String, r#type: PyObject) -> Self {
-        Self { name, r#type }
+    fn new(py: Python, name: String, r#type: PyObject) -> Self {
+        Self {
+            name,
+            r#type,
+            function_body: py.None(),
+        }
     }
 }
 
@@ -445,6 +595,7 @@ impl TryFrom<wasmer::ExportType> for ExportType {
         Ok(Self {
             name: value.name().to_string(),
             r#type: extern_type_to_py_object(py, value.ty())?,
+            function_body: py.None(),
         })
     }
 }
@@ -549,3 +700,24 @@ fn extern_type_to_py_object(py: Python, value: &wasmer::ExternType) -> PyResult<
         wasmer::ExternType::Memory(t) => Py::new(py, MemoryType::from(t))?.to_object(py),
     })
 }
+
+/// Builds a `FunctionBody(local_count, body_size)` named tuple, see
+/// `ExportType.function_body`.
+pub(crate) fn function_body(py: Python, local_count: u32, body_size: usize) -> PyResult<PyObject> {
+    static FUNCTION_BODY: GILOnceCell<PyObject> = GILOnceCell::new();
+
+    let function_body = match FUNCTION_BODY.get(py) {
+        Some(function_body) => function_body,
+        None => {
+            let function_body = py
+                .import("collections")?
+                .call_method1("namedtuple", ("FunctionBody", ["local_count", "body_size"]))?
+                .to_object(py);
+            let _ = FUNCTION_BODY.set(py, function_body);
+
+            FUNCTION_BODY.get(py).unwrap()
+        }
+    };
+
+    function_body.call1(py, (local_count, body_size))
+}
@@ -1,8 +1,15 @@
-use crate::wasmer_inner::{wasmer, wasmer_types::NativeWasmType};
+use crate::{
+    types::Type,
+    wasmer_inner::{
+        wasmer::{self, Exportable},
+        wasmer_types::NativeWasmType,
+    },
+};
 use pyo3::{
     class::basic::PyObjectProtocol,
     prelude::*,
-    types::{PyFloat, PyLong},
+    types::{PyBool, PyFloat, PyLong},
+    PyNativeType, PyTryFrom,
 };
 
 pub trait NativeFromPyAny {
@@ -15,7 +22,7 @@ impl NativeFromPyAny for i32 {
     type Native = Self;
 
     fn from_pyany(any: &PyAny) -> PyResult<Self::Native> {
-        any.downcast::<PyLong>()?.extract::<Self::Native>()
+        downcast_scalar::<PyLong>(any)?.extract::<Self::Native>()
     }
 }
 
@@ -23,7 +30,7 @@ impl NativeFromPyAny for i64 {
     type Native = Self;
 
     fn from_pyany(any: &PyAny) -> PyResult<Self::Native> {
-        any.downcast::<PyLong>()?.extract::<Self::Native>()
+        downcast_scalar::<PyLong>(any)?.extract::<Self::Native>()
     }
 }
 
@@ -31,7 +38,11 @@ impl NativeFromPyAny for f32 {
     type Native = Self;
 
     fn from_pyany(any: &PyAny) -> PyResult<Self::Native> {
-        any.downcast::<PyFloat>()?.extract::<Self::Native>()
+        match downcast_scalar::<PyFloat>(any) {
+            Ok(float) => float.extract::<Self::Native>(),
+            Err(_) if is_decimal_or_fraction(any)? => any.extract::<Self::Native>(),
+            Err(error) => Err(error),
+        }
     }
 }
 
@@ -39,15 +50,59 @@ impl NativeFromPyAny for f64 {
     type Native = Self;
 
     fn from_pyany(any: &PyAny) -> PyResult<Self::Native> {
-        any.downcast::<PyFloat>()?.extract::<Self::Native>()
+        match downcast_scalar::<PyFloat>(any) {
+            Ok(float) => float.extract::<Self::Native>(),
+            Err(_) if is_decimal_or_fraction(any)? => any.extract::<Self::Native>(),
+            Err(error) => Err(error),
+        }
     }
 }
 
+/// Downcasts `any` to a Python scalar. A NumPy scalar, like
+/// `numpy.int32(5)`, is first converted to its Python scalar with its
+/// `item` method. NumPy is not imported: a NumPy scalar is recognized
+/// by its type, defined in the `numpy` module.
+fn downcast_scalar<T>(any: &PyAny) -> PyResult<&T>
+where
+    for<'py> T: PyTryFrom<'py>,
+{
+    match any.downcast::<T>() {
+        Ok(scalar) => Ok(scalar),
+        Err(error) => {
+            let is_numpy_scalar = any
+                .get_type()
+                .getattr("__module__")
+                .and_then(|module| module.extract::<&str>())
+                .map(|module| module == "numpy")
+                .unwrap_or(false);
+
+            if is_numpy_scalar && any.hasattr("item")? {
+                Ok(any.call_method0("item")?.downcast::<T>()?)
+            } else {
+                Err(error.into())
+            }
+        }
+    }
+}
+
+/// Checks whether `any` is a `decimal.Decimal` or a
+/// `fractions.Fraction`, which are converted with `float()` when a
+/// float is expected.
+fn is_decimal_or_fraction(any: &PyAny) -> PyResult<bool> {
+    let py = any.py();
+    let decimal = py.import("decimal")?.getattr("Decimal")?;
+    let fraction = py.import("fractions")?.getattr("Fraction")?;
+
+    py.import("builtins")?
+        .call1("isinstance", (any, (decimal, fraction)))?
+        .extract::<bool>()
+}
+
 impl NativeFromPyAny for u128 {
     type Native = Self;
 
     fn from_pyany(any: &PyAny) -> PyResult<Self::Native> {
-        any.downcast::<PyLong>()?.extract::<Self::Native>()
+        downcast_scalar::<PyLong>(any)?.extract::<Self::Native>()
     }
 }
 
@@ -77,6 +132,31 @@ pub(crate) fn to_wasm_value((any, ty): (&PyAny, wasmer::Type)) -> PyResult<wasme
     })
 }
 
+/// Checks that `any` is a value of the type `ty` without any implicit
+/// conversion, see `Store(strict_marshaling=True)`. An `int` and a
+/// `float` are never converted into each other, so only a `bool` or
+/// a NumPy scalar given for an integer, and a `Decimal`, a `Fraction`
+/// or a NumPy scalar given for a float, are concerned.
+pub(crate) fn is_strictly_of_type(any: &PyAny, ty: wasmer::Type) -> bool {
+    match ty {
+        wasmer::Type::I32 | wasmer::Type::I64 | wasmer::Type::V128 => {
+            any.is_instance::<PyLong>().unwrap_or(false)
+                && !any.is_instance::<PyBool>().unwrap_or(false)
+        }
+        wasmer::Type::F32 | wasmer::Type::F64 => any.is_instance::<PyFloat>().unwrap_or(false),
+        _ => true,
+    }
+}
+
+/// Returns the name of the Python type expected for a value of the
+/// type `ty`.
+pub(crate) fn python_type_name(ty: wasmer::Type) -> &'static str {
+    match ty {
+        wasmer::Type::F32 | wasmer::Type::F64 => "float",
+        _ => "int",
+    }
+}
+
 pub(crate) fn to_py_object<'p>(py: Python<'p>) -> impl Fn(&wasmer::Value) -> PyObject + 'p {
     move |value: &wasmer::Value| -> PyObject {
         match value {
@@ -108,6 +188,10 @@ pub struct Value {
 }
 
 impl Value {
+    pub(crate) fn raw_new(inner: wasmer::Value) -> Self {
+        Self { inner }
+    }
+
     pub(crate) fn inner(&self) -> &wasmer::Value {
         &self.inner
     }
@@ -199,6 +283,96 @@ impl Value {
             inner: wasmer::Value::V128(value),
         }
     }
+
+    /// Build a null WebAssembly reference value, of type `externref`.
+    ///
+    /// It can be used to initialize the elements of a `Table`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.null()
+    ///
+    /// assert value.is_null
+    /// ```
+    #[staticmethod]
+    #[text_signature = "()"]
+    fn null() -> Self {
+        Self {
+            inner: wasmer::Value::null(),
+        }
+    }
+
+    /// The type of the value, as a `Type`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value, Type
+    ///
+    /// assert Value.i32(42).type == Type.I32
+    /// assert Value.f64(4.2).type == Type.F64
+    /// ```
+    #[getter(type)]
+    fn ty(&self) -> Type {
+        Type::from(&self.inner.ty())
+    }
+
+    /// The value as a Python number, or `None` for a reference.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// assert Value.i32(42).value == 42
+    /// assert Value.f64(4.2).value == 4.2
+    /// assert Value.null().value == None
+    /// ```
+    #[getter]
+    fn value(&self, py: Python) -> PyObject {
+        match self.inner {
+            wasmer::Value::ExternRef(_) | wasmer::Value::FuncRef(_) => py.None(),
+            ref value => to_py_object(py)(value),
+        }
+    }
+
+    /// Checks whether the value is a null reference, be it an
+    /// `externref` or a `funcref`.
+    ///
+    /// A `Value` is falsy when it is a null reference, and truthy
+    /// otherwise, including for numbers, so that `if value:` can be
+    /// used when handling reference values.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// assert Value.null().is_null
+    /// assert not Value.null()
+    ///
+    /// assert not Value.i32(0).is_null
+    /// assert Value.i32(0)
+    /// ```
+    #[getter]
+    fn is_null(&self) -> bool {
+        match &self.inner {
+            wasmer::Value::ExternRef(wasmer::ExternRef::Null) => true,
+
+            // Wasmer reads a null `funcref` as a null `externref`,
+            // but a `funcref` to a function without a body is null
+            // too.
+            wasmer::Value::FuncRef(function) => matches!(
+                function.to_export(),
+                wasmer::Export::Function(function) if function.vm_function.address.is_null()
+            ),
+
+            _ => false,
+        }
+    }
 }
 
 #[pyproto]
@@ -206,4 +380,8 @@ impl PyObjectProtocol for Value {
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self.inner()))
     }
+
+    fn __bool__(&self) -> bool {
+        !self.is_null()
+    }
 }
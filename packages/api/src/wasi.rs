@@ -1,15 +1,24 @@
 use crate::{
-    errors::to_py_err, import_object::ImportObject, module::Module, store::Store,
-    wasmer_inner::wasmer_wasi,
+    errors::to_py_err,
+    import_object::ImportObject,
+    instance::Instance,
+    module::Module,
+    store::Store,
+    wasmer_inner::{wasmer, wasmer_wasi},
 };
 use pyo3::{
-    exceptions::{PyRuntimeError, PyValueError},
+    exceptions::{PyLookupError, PyRuntimeError, PyValueError},
     prelude::*,
-    types::{PyDict, PyList},
+    types::{PyBytes, PyDict, PyList},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, Read, Seek, Write},
+    path::PathBuf,
+    slice,
 };
-use std::{path::PathBuf, slice};
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq)]
 #[repr(u8)]
 pub enum Version {
     Latest = 1,
@@ -407,6 +416,63 @@ impl StateBuilder {
         Ok(slf)
     }
 
+    /// Calls `callback` with the bytes written by the program to its
+    /// standard output, chunk by chunk as they are written, instead
+    /// of writing them to the standard output of the host. It is
+    /// handy to report the progress of a long-running program.
+    ///
+    /// The callback is called synchronously, on the thread running
+    /// the program, during its WASI write calls: the program waits
+    /// for the callback to return. The callback must not call the
+    /// instance running the program, since the WASI state is locked
+    /// during the write. If the callback raises an exception, the
+    /// write fails in the program.
+    ///
+    /// This method returns `self`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Module
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     r"""
+    ///     (module
+    ///       (import "wasi_snapshot_preview1" "fd_write"
+    ///         (func $fd_write (param i32 i32 i32 i32) (result i32)))
+    ///       (memory (export "memory") 1)
+    ///       ;; An I/O vector pointing to the string at offset 16.
+    ///       (data (i32.const 0) "\10\00\00\00\06\00\00\00")
+    ///       (data (i32.const 16) "hello\n")
+    ///       (func (export "_start")
+    ///         (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))
+    ///         (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))))
+    ///     """
+    /// )
+    ///
+    /// chunks = []
+    /// wasi_env = wasi.StateBuilder('test-program').stdout(chunks.append).finalize()
+    ///
+    /// assert wasi_env.run(module) == 0
+    /// assert chunks == [b'hello\n', b'hello\n']
+    /// ```
+    #[text_signature = "($self, callback)"]
+    pub fn stdout<'py>(slf: &'py PyCell<Self>, callback: &PyAny) -> PyResult<&'py PyCell<Self>> {
+        if !callback.is_callable() {
+            return Err(to_py_err::<PyValueError, _>(
+                "The stdout callback must be a callable",
+            ));
+        }
+
+        let mut slf_mut = slf.try_borrow_mut()?;
+        slf_mut.inner.stdout(Box::new(OutputCallback {
+            callback: Some(callback.into()),
+        }));
+
+        Ok(slf)
+    }
+
     /// Produces a WASI `Environment` based on this state builder.
     ///
     /// ## Example
@@ -463,14 +529,197 @@ impl Environment {
     /// import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
     /// ```
     //#[text_signature = "($self, store, wasi_version)"]
-    fn generate_import_object(&self, store: &Store, wasi_version: Version) -> ImportObject {
+    fn generate_import_object(
+        &self,
+        store: &Store,
+        wasi_version: Version,
+    ) -> PyResult<ImportObject> {
         let import_object = wasmer_wasi::generate_import_object_from_env(
-            store.inner(),
+            store.inner()?,
             self.inner.clone(),
             wasi_version.into(),
         );
 
-        ImportObject::raw_new(import_object)
+        Ok(ImportObject::raw_new(import_object))
+    }
+
+    /// Runs a WASI program: it instantiates the `wasmer.Module` with
+    /// the WASI imports generated from this `Environment` (the WASI
+    /// version is detected with `get_version`), calls the `_start`
+    /// function, and returns the exit code of the program.
+    ///
+    /// The program arguments and environment variables are the ones
+    /// given to the `StateBuilder`. Like a process, a nonzero exit
+    /// code is returned, not raised.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Module
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+    ///       (memory (export "memory") 1)
+    ///       (func (export "_start")
+    ///         i32.const 42
+    ///         call $proc_exit))
+    ///     """
+    /// )
+    ///
+    /// wasi_env = wasi.StateBuilder('test-program').argument('--foo').finalize()
+    ///
+    /// assert wasi_env.run(module) == 42
+    /// ```
+    #[text_signature = "($self, module)"]
+    fn run(&self, module: &Module) -> PyResult<u32> {
+        let module = module.inner();
+        let import_object = self
+            .inner
+            .clone()
+            .import_object(module)
+            .map_err(to_py_err::<PyValueError, _>)?;
+        let instance = wasmer::Instance::new(module, &import_object)
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+        let start = instance
+            .exports
+            .get_function("_start")
+            .map_err(to_py_err::<PyLookupError, _>)?;
+
+        match start.call(&[]) {
+            Ok(_) => Ok(0),
+            Err(error) => match error.downcast::<wasmer_wasi::WasiError>() {
+                Ok(wasmer_wasi::WasiError::Exit(exit_code)) => Ok(exit_code),
+                Ok(error) => Err(to_py_err::<PyRuntimeError, _>(error)),
+                Err(error) => Err(to_py_err::<PyRuntimeError, _>(error)),
+            },
+        }
+    }
+
+    /// Instantiates a WASI module in one step: the WASI imports are
+    /// generated from this `Environment` for the WASI version of the
+    /// module, and the module is instantiated with them. It returns
+    /// the `wasmer.Instance`.
+    ///
+    /// A `ValueError` is raised if the module is not a WASI module,
+    /// or if `wasi_version` is given but does not match the WASI
+    /// version of the module.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Module, Instance
+    ///
+    /// store = Store()
+    /// module = Module(store, open('tests/wasi.wasm', 'rb').read())
+    ///
+    /// wasi_env = wasi.StateBuilder('test-program').argument('--foo').finalize()
+    /// instance = wasi_env.instantiate(module)
+    ///
+    /// assert isinstance(instance, Instance)
+    /// ```
+    #[text_signature = "($self, module, wasi_version)"]
+    #[args(wasi_version = "None")]
+    fn instantiate(
+        &self,
+        py: Python,
+        module: &Module,
+        wasi_version: Option<Version>,
+    ) -> PyResult<Instance> {
+        let module_version = get_version(module, false).ok_or_else(|| {
+            to_py_err::<PyValueError, _>("The module is not a WASI module, no WASI version found")
+        })?;
+
+        if let Some(wasi_version) = wasi_version {
+            if wasi_version != module_version {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The module requires the WASI version `{}`, but `{}` was given",
+                    <&str>::from(&module_version),
+                    <&str>::from(&wasi_version),
+                )));
+            }
+        }
+
+        let import_object = ImportObject::raw_new(wasmer_wasi::generate_import_object_from_env(
+            module.inner().store(),
+            self.inner.clone(),
+            module_version.into(),
+        ));
+
+        Ok(Instance::raw_new(py, module, Some(&import_object))?)
+    }
+}
+
+/// A WASI output file that calls a Python callback with the written
+/// bytes, see `StateBuilder.stdout`. The callback cannot be
+/// serialized, so it is lost when the WASI state is serialized.
+#[derive(Debug, Serialize, Deserialize)]
+struct OutputCallback {
+    #[serde(skip)]
+    callback: Option<PyObject>,
+}
+
+impl Read for OutputCallback {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("can not read from an output"))
+    }
+}
+
+impl Seek for OutputCallback {
+    fn seek(&mut self, _pos: io::SeekFrom) -> io::Result<u64> {
+        Err(io::Error::other("can not seek an output"))
+    }
+}
+
+impl Write for OutputCallback {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(callback) = &self.callback {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+
+            callback
+                .call1(py, (PyBytes::new(py, buf),))
+                .map_err(io::Error::from)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[typetag::serde]
+impl wasmer_wasi::WasiFile for OutputCallback {
+    fn last_accessed(&self) -> u64 {
+        0
+    }
+
+    fn last_modified(&self) -> u64 {
+        0
+    }
+
+    fn created_time(&self) -> u64 {
+        0
+    }
+
+    fn size(&self) -> u64 {
+        0
+    }
+
+    fn set_len(&mut self, _new_size: u64) -> Result<(), wasmer_wasi::WasiFsError> {
+        Err(wasmer_wasi::WasiFsError::PermissionDenied)
+    }
+
+    fn unlink(&mut self) -> Result<(), wasmer_wasi::WasiFsError> {
+        Ok(())
+    }
+
+    fn bytes_available(&self) -> Result<usize, wasmer_wasi::WasiFsError> {
+        Ok(0)
     }
 }
 
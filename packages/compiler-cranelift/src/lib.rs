@@ -1,7 +1,5 @@
 use pyo3::prelude::*;
 
-use wasmer_engines::OpaqueCompiler;
-
 /// The Cranelift compiler, designed for the `wasmer` Python package
 /// (a WebAssembly runtime).
 ///
@@ -26,14 +24,4 @@ fn wasmer_compiler_cranelift(_py: Python, module: &PyModule) -> PyResult<()> {
 #[pyclass]
 struct Compiler {}
 
-#[pymethods]
-impl Compiler {
-    /// Please don't use it. Internal use only.
-    #[staticmethod]
-    fn into_opaque_compiler() -> OpaqueCompiler {
-        OpaqueCompiler::raw_with_compiler(
-            wasmer_compiler_cranelift::Cranelift::default(),
-            "cranelift".to_string(),
-        )
-    }
-}
+wasmer_engines::impl_compiler!(Compiler, wasmer_compiler_cranelift::Cranelift, "cranelift");
@@ -1,7 +1,5 @@
 use pyo3::prelude::*;
 
-use wasmer_engines::OpaqueCompiler;
-
 /// The Singlepass compiler, designed for the `wasmer` Python package
 /// (a WebAssembly runtime).
 ///
@@ -26,14 +24,8 @@ fn wasmer_compiler_singlepass(_py: Python, module: &PyModule) -> PyResult<()> {
 #[pyclass]
 struct Compiler {}
 
-#[pymethods]
-impl Compiler {
-    /// Please don't use it. Internal use only.
-    #[staticmethod]
-    fn into_opaque_compiler() -> OpaqueCompiler {
-        OpaqueCompiler::raw_with_compiler(
-            wasmer_compiler_singlepass::Singlepass::default(),
-            "singlepass".to_string(),
-        )
-    }
-}
+wasmer_engines::impl_compiler!(
+    Compiler,
+    wasmer_compiler_singlepass::Singlepass,
+    "singlepass"
+);
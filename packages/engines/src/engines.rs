@@ -1,7 +1,31 @@
 use crate::target_lexicon::Target;
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    prelude::*,
+};
 use std::mem::ManuallyDrop;
 
+/// Gets the opaque compiler from `compiler`. The compiler options are
+/// only passed when they differ from the defaults, so that compiler
+/// packages predating them keep working.
+fn into_opaque_compiler(compiler: &PyAny, canonicalize_nans: bool) -> PyResult<&PyAny> {
+    if !canonicalize_nans {
+        return compiler.call_method0("into_opaque_compiler");
+    }
+
+    if !compiler.hasattr("into_opaque_compiler_with_options")? {
+        return Err(PyRuntimeError::new_err(
+            "The compiler does not support `canonicalize_nans`, please upgrade it",
+        ));
+    }
+
+    compiler.call_method1("into_opaque_compiler_with_options", (canonicalize_nans,))
+}
+
+fn canonicalize_nans_without_compiler() -> PyErr {
+    PyValueError::new_err("`canonicalize_nans` requires a compiler")
+}
+
 /// JIT engine for Wasmer compilers.
 ///
 /// Given an optional compiler, it generates the compiled machine code,
@@ -11,19 +35,34 @@ use std::mem::ManuallyDrop;
 ///
 /// It is possible to specify a `Target` to possibly cross-compile for
 /// a different target. It requires a compiler.
+///
+/// Setting `canonicalize_nans` to `True` forces the compiler to
+/// canonicalize NaN values produced by floating-point operations
+/// where the WebAssembly specification leaves the bit pattern to the
+/// implementation. Float results are then bit-identical regardless
+/// of the host CPU, at the cost of some performance. It requires a
+/// compiler, otherwise a `ValueError` is raised. It is an option of
+/// the engine rather than of the `Store`, because it is given to the
+/// compiler: the modules are compiled by the engine, and a headless
+/// engine does not compile anything.
 #[pyclass(unsendable)]
-#[text_signature = "(/, compiler, target)"]
+#[text_signature = "(/, compiler, target, canonicalize_nans)"]
 pub struct JIT {
     inner: wasmer::JITEngine,
     compiler_name: Option<String>,
 }
 
 impl JIT {
-    pub fn raw_new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
+    pub fn raw_new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        canonicalize_nans: bool,
+    ) -> PyResult<Self> {
         let (inner, compiler_name) = match compiler {
+            None if canonicalize_nans => return Err(canonicalize_nans_without_compiler()),
             None => (wasmer::JIT::headless().engine(), None),
             Some(compiler) => {
-                let opaque_compiler = compiler.call_method0("into_opaque_compiler")?;
+                let opaque_compiler = into_opaque_compiler(compiler, canonicalize_nans)?;
                 let opaque_compiler_inner_ptr = opaque_compiler
                     .call_method0("__inner_as_ptr")?
                     .extract::<usize>()?;
@@ -85,8 +124,13 @@ impl JIT {
 #[pymethods]
 impl JIT {
     #[new]
-    fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        Self::raw_new(compiler, target)
+    #[args(compiler = "None", target = "None", canonicalize_nans = "false")]
+    fn new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        canonicalize_nans: bool,
+    ) -> PyResult<Self> {
+        Self::raw_new(compiler, target, canonicalize_nans)
     }
 }
 
@@ -101,19 +145,31 @@ impl JIT {
 ///
 /// It is possible to specify a `Target` to possibly cross-compile for
 /// a different target. It requires a compiler.
+///
+/// Setting `canonicalize_nans` to `True` forces the compiler to
+/// canonicalize NaN values produced by floating-point operations
+/// where the WebAssembly specification leaves the bit pattern to the
+/// implementation. Float results are then bit-identical regardless
+/// of the host CPU, at the cost of some performance. It requires a
+/// compiler, otherwise a `ValueError` is raised, see `JIT`.
 #[pyclass(unsendable)]
-#[text_signature = "(/, compiler, target)"]
+#[text_signature = "(/, compiler, target, canonicalize_nans)"]
 pub struct Native {
     inner: wasmer::NativeEngine,
     compiler_name: Option<String>,
 }
 
 impl Native {
-    pub fn raw_new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
+    pub fn raw_new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        canonicalize_nans: bool,
+    ) -> PyResult<Self> {
         let (inner, compiler_name) = match compiler {
+            None if canonicalize_nans => return Err(canonicalize_nans_without_compiler()),
             None => (wasmer::Native::headless().engine(), None),
             Some(compiler) => {
-                let opaque_compiler = compiler.call_method0("into_opaque_compiler")?;
+                let opaque_compiler = into_opaque_compiler(compiler, canonicalize_nans)?;
                 let opaque_compiler_inner_ptr = opaque_compiler
                     .call_method0("__inner_as_ptr")?
                     .extract::<usize>()?;
@@ -175,8 +231,13 @@ impl Native {
 #[pymethods]
 impl Native {
     #[new]
-    fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        Self::raw_new(compiler, target)
+    #[args(compiler = "None", target = "None", canonicalize_nans = "false")]
+    fn new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        canonicalize_nans: bool,
+    ) -> PyResult<Self> {
+        Self::raw_new(compiler, target, canonicalize_nans)
     }
 }
 
@@ -207,6 +268,42 @@ impl OpaqueCompiler {
     }
 }
 
+/// Implements the `into_opaque_compiler` and
+/// `into_opaque_compiler_with_options` static methods of a
+/// `Compiler` class, given the type of the configuration of the
+/// compiler and its name. Each compiler package defines its
+/// `Compiler` class with it.
+#[macro_export]
+macro_rules! impl_compiler {
+    ($compiler:ident, $compiler_config:ty, $compiler_name:expr) => {
+        #[pyo3::prelude::pymethods]
+        impl $compiler {
+            /// Please don't use it. Internal use only.
+            #[staticmethod]
+            fn into_opaque_compiler() -> $crate::OpaqueCompiler {
+                $crate::OpaqueCompiler::raw_with_compiler(
+                    <$compiler_config>::default(),
+                    $compiler_name.to_string(),
+                )
+            }
+
+            /// Please don't use it. Internal use only.
+            #[staticmethod]
+            fn into_opaque_compiler_with_options(
+                canonicalize_nans: bool,
+            ) -> $crate::OpaqueCompiler {
+                let mut compiler_config = <$compiler_config>::default();
+                compiler_config.canonicalize_nans(canonicalize_nans);
+
+                $crate::OpaqueCompiler::raw_with_compiler(
+                    compiler_config,
+                    $compiler_name.to_string(),
+                )
+            }
+        }
+    };
+}
+
 #[pymethods]
 impl OpaqueCompiler {
     pub fn __inner_as_ptr(&self) -> usize {
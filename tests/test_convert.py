@@ -0,0 +1,49 @@
+from wasmer import convert, Type, Value
+import pytest
+
+def test_to_wasm_i32():
+    assert repr(convert.to_wasm(42, Type.I32)) == 'I32(42)'
+
+def test_to_wasm_i64():
+    assert repr(convert.to_wasm(9223372036854775807, Type.I64)) == 'I64(9223372036854775807)'
+
+def test_to_wasm_f32():
+    assert repr(convert.to_wasm(4.2, Type.F32)) == 'F32(4.2)'
+
+def test_to_wasm_f64():
+    assert repr(convert.to_wasm(4.2, Type.F64)) == 'F64(4.2)'
+
+def test_to_wasm_v128():
+    assert repr(convert.to_wasm(42, Type.V128)) == 'V128(42)'
+
+def test_to_wasm_invalid_value():
+    with pytest.raises(TypeError):
+        convert.to_wasm('foo', Type.I32)
+
+def test_to_wasm_overflow():
+    with pytest.raises(OverflowError):
+        convert.to_wasm(2 ** 31, Type.I32)
+
+def test_to_wasm_reference_type():
+    with pytest.raises(TypeError) as context_manager:
+        convert.to_wasm(42, Type.EXTERN_REF)
+
+    exception = context_manager.value
+    assert str(exception) == 'Cannot convert a Python object to a `ExternRef` value'
+
+def test_to_python():
+    assert convert.to_python(Value.i32(42)) == 42
+    assert convert.to_python(Value.i64(42)) == 42
+    assert convert.to_python(Value.f32(4.5)) == 4.5
+    assert convert.to_python(Value.f64(4.2)) == 4.2
+    assert convert.to_python(Value.v128(42)) == 42
+
+def test_to_python_reference_type():
+    with pytest.raises(TypeError) as context_manager:
+        convert.to_python(Value.null())
+
+    exception = context_manager.value
+    assert str(exception) == 'Cannot convert a `ExternRef` value to a Python object'
+
+def test_round_trip():
+    assert convert.to_python(convert.to_wasm(-7, Type.I64)) == -7
@@ -1,5 +1,9 @@
 import wasmer
-from wasmer import Instance, Module, Store, Function, FunctionType, Type, ImportObject
+from wasmer import Instance, Module, Store, Function, FunctionType, Type, ImportObject, HostTrap, Value
+from enum import IntEnum, IntFlag
+from decimal import Decimal
+from fractions import Fraction
+import functools
 import os
 import pytest
 
@@ -19,6 +23,35 @@ def test_constructor_with_annotated_function():
     store = Store()
     function = Function(store, sum)
 
+def test_constructor_with_annotated_function_without_parameters():
+    import time
+
+    def now() -> int:
+        return time.time_ns() & 0x7fffffff
+
+    store = Store()
+    function = Function(store, now)
+
+    assert function.type.params == []
+    assert function.type.results == [Type.I32]
+
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "now" (func $now (result i32)))
+          (func (export "elapsed") (result i32)
+            call $now
+            call $now
+            i32.sub))
+        """
+    )
+    import_object = ImportObject()
+    import_object.register("env", {"now": function})
+    instance = Instance(module, import_object)
+
+    assert isinstance(instance.exports.elapsed(), int)
+
 def test_constructor_with_blank_function():
     def sum(x, y):
         return x + y
@@ -26,6 +59,127 @@ def test_constructor_with_blank_function():
     store = Store()
     function = Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I32]))
 
+def test_constructor_with_annotated_function_and_matching_function_type():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    store = Store()
+    function = Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I32]))
+
+    assert function.type.params == [Type.I32, Type.I32]
+    assert function.type.results == [Type.I32]
+
+def test_constructor_with_annotated_function_and_wider_function_type():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    def half(x: float) -> float:
+        return x / 2
+
+    store = Store()
+
+    # `int` and `float` are compatible with both widths.
+    assert Function(store, sum, FunctionType([Type.I64, Type.I64], [Type.I64])).type.params == [Type.I64, Type.I64]
+    assert Function(store, half, FunctionType([Type.F64], [Type.F64])).type.results == [Type.F64]
+
+def test_constructor_with_annotated_function_and_conflicting_function_type():
+    def half(x: float) -> float:
+        return x / 2
+
+    store = Store()
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(store, half, FunctionType([Type.I32], [Type.F32]))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The annotation `float` of the parameter `x` does not match the parameter #0 of type `I32` of the function type'
+    )
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(store, half, FunctionType([Type.F32], [Type.I64]))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The return annotation `float` does not match the results `[I64]` of the function type'
+    )
+
+def test_constructor_with_named_annotations_and_conflicting_function_type():
+    def sum(x: 'i32', y: 'i64') -> 'i64':
+        return x + y
+
+    store = Store()
+
+    assert Function(store, sum, FunctionType([Type.I32, Type.I64], [Type.I64])).type.params == [Type.I32, Type.I64]
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I64]))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The annotation `i64` of the parameter `y` does not match the parameter #1 of type `I32` of the function type'
+    )
+
+def test_constructor_with_annotated_function_and_shorter_function_type():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    with pytest.raises(ValueError) as context_manager:
+        Function(Store(), sum, FunctionType([Type.I32], [Type.I32]))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The annotated parameter `y` has no matching parameter in the function type `[I32]`'
+    )
+
+def test_constructor_with_partially_annotated_function_and_function_type():
+    def sum(x, y) -> int:
+        return x + y
+
+    store = Store()
+    function = Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I32]))
+
+    assert function.type.params == [Type.I32, Type.I32]
+
+    # Only the annotated parameter is checked, at its position.
+    def scale(x, factor: float):
+        return x * factor
+
+    assert Function(store, scale, FunctionType([Type.I32, Type.F64], [])).type.params == [Type.I32, Type.F64]
+
+    with pytest.raises(ValueError):
+        Function(store, scale, FunctionType([Type.F64, Type.I32], []))
+
+def test_constructor_with_deterministic():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    store = Store()
+
+    assert Function(store, sum, deterministic=True).deterministic == True
+    assert Function(store, sum, FunctionType([Type.I32, Type.I32], [Type.I32]), True).deterministic == True
+    assert Function(store, sum).deterministic == False
+
+def test_exported_function_is_not_deterministic():
+    assert instance().exports.sum.deterministic == False
+
+def test_exported_function_name():
+    exports = instance().exports
+
+    assert exports.sum.__name__ == 'sum'
+    assert exports.arity_0.__name__ == 'arity_0'
+
+def test_host_function_name():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    store = Store()
+    function_type = FunctionType([Type.I32, Type.I32], [Type.I32])
+
+    assert Function(store, sum).__name__ == 'sum'
+    assert Function(store, lambda x, y: x + y, function_type).__name__ == '<lambda>'
+    assert Function(store, functools.partial(sum, 1), FunctionType([Type.I32], [Type.I32])).__name__ == 'partial'
+
 def test_export():
     assert isinstance(instance().exports.sum, Function)
 
@@ -40,6 +194,121 @@ def test_type():
 def test_basic_sum():
     assert value_with_type(instance().exports.sum(1, 2)) == (3, int)
 
+def test_call_with_expected_results():
+    assert instance().exports.sum(1, 2, expected_results=[Type.I32]) == 3
+
+def test_call_with_unexpected_results():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum(1, 2, expected_results=[Type.I64])
+
+    exception = context_manager.value
+    assert str(exception) == 'The function results are `[I32]`, but `[I64]` were expected'
+
+def test_call_with_unexpected_keyword_argument():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum(1, 2, foo=[Type.I32])
+
+    exception = context_manager.value
+    assert str(exception) == '`Function.__call__` got an unexpected keyword argument `foo`'
+
+def test_call_void_with_expected_results():
+    assert instance().exports.void(expected_results=[]) == None
+
+def test_apply_list():
+    assert value_with_type(instance().exports.sum.apply([1, 2])) == (3, int)
+
+def test_apply_tuple():
+    assert value_with_type(instance().exports.sum.apply((1, 2))) == (3, int)
+
+def test_apply_not_a_sequence():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum.apply(1)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        '`Function.apply` expects a list or a tuple of arguments'
+    )
+
+def test_apply_rejects_other_sequences():
+    for arguments in ['12', b'\x01\x02', range(1, 3)]:
+        with pytest.raises(TypeError) as context_manager:
+            instance().exports.sum.apply(arguments)
+
+        exception = context_manager.value
+        assert str(exception) == (
+            '`Function.apply` expects a list or a tuple of arguments'
+        )
+
+def test_check_args():
+    assert instance().exports.sum.check_args(1, 2) == None
+
+def test_check_args_raises_as_a_call():
+    sum = instance().exports.sum
+
+    for arguments in [(), (1,), (1, 2, 3), (1, 'two'), (1, 2**32)]:
+        with pytest.raises(Exception) as call_context_manager:
+            sum(*arguments)
+
+        with pytest.raises(Exception) as check_context_manager:
+            sum.check_args(*arguments)
+
+        call_exception = call_context_manager.value
+        check_exception = check_context_manager.value
+        assert type(check_exception) == type(call_exception)
+        assert str(check_exception) == str(call_exception)
+
+def test_call_with_a_wrong_number_of_arguments():
+    exports = instance().exports
+    sum = exports.sum
+
+    for arguments in [(1,), (1, 2, 3)]:
+        for call in [
+            lambda: sum(*arguments),
+            lambda: sum.apply(list(arguments)),
+            lambda: sum.check_args(*arguments),
+        ]:
+            with pytest.raises(RuntimeError) as context_manager:
+                call()
+
+            exception = context_manager.value
+            assert str(exception) == (
+                f'The function expects 2 argument(s), got {len(arguments)} '
+                '(parameters of the function: [I32, I32])'
+            )
+
+def test_check_args_does_not_call():
+    module = Module(
+        Store(),
+        """
+        (module
+          (global $calls (export "calls") (mut i32) (i32.const 0))
+          (func (export "increment") (param i32)
+            global.get $calls
+            local.get 0
+            i32.add
+            global.set $calls))
+        """
+    )
+    exports = Instance(module).exports
+
+    exports.increment.check_args(1)
+
+    assert exports.calls.value == 0
+
+def test_call_with_metrics():
+    result, points_used, elapsed_ns = instance().exports.sum.call_with_metrics(1, 2)
+
+    assert result == 3
+    assert points_used == None
+    assert isinstance(elapsed_ns, int)
+    assert elapsed_ns >= 0
+
+def test_call_with_metrics_signature():
+    assert Function.call_with_metrics.__text_signature__ == '($self, *arguments)'
+
+def test_call_with_metrics_void():
+    assert instance().exports.void.call_with_metrics()[0] == None
+
 def test_call_arity_0():
     assert value_with_type(instance().exports.arity_0()) == (42, int)
 
@@ -49,17 +318,179 @@ def test_call_i32_i32():
 def test_call_i64_i64():
     assert value_with_type(instance().exports.i64_i64(7)) == (7, int)
 
+def test_call_i64_i64_bounds():
+    i64_i64 = instance().exports.i64_i64
+
+    assert i64_i64(9223372036854775807) == 9223372036854775807
+    assert i64_i64(-9223372036854775808) == -9223372036854775808
+
+def test_call_i64_i64_overflow():
+    with pytest.raises(OverflowError) as context_manager:
+        instance().exports.i64_i64(9223372036854775808)
+
+    exception = context_manager.value
+    assert str(exception) == 'Argument #0 (`9223372036854775808`) overflows the `I64` type'
+
+def test_call_i64_i64_underflow():
+    with pytest.raises(OverflowError) as context_manager:
+        instance().exports.i64_i64(-9223372036854775809)
+
+    exception = context_manager.value
+    assert str(exception) == 'Argument #0 (`-9223372036854775809`) overflows the `I64` type'
+
+def test_call_with_values():
+    exports = instance().exports
+
+    assert value_with_type(exports.i32_i32(Value.i32(7))) == (7, int)
+    assert value_with_type(exports.i64_i64(Value.i64(7))) == (7, int)
+    assert value_with_type(exports.f64_f64(Value.f64(7.5))) == (7.5, float)
+    assert exports.i32_i64_f32_f64_f64(1, Value.i64(2), 3.5, Value.f64(5.5)) == 1 + 2 + 3.5 + 5.5
+
+def test_call_with_values_and_strict_marshaling():
+    exports = Instance(Module(Store(strict_marshaling=True), TEST_BYTES)).exports
+
+    assert exports.i64_i64(Value.i64(7)) == 7
+
+def test_call_with_value_of_another_type():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.i64_i64(Value.i32(7))
+
+    exception = context_manager.value
+    assert str(exception) == 'Argument #0 must be a value of type `I64`, got `I32`'
+
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.f32_f32(Value.f64(7.5))
+
+    exception = context_manager.value
+    assert str(exception) == 'Argument #0 must be a value of type `F32`, got `F64`'
+
+def test_call_large_i64_results():
+    exports = Instance(
+        Module(
+            Store(),
+            """
+            (module
+              (func (export "above_2_53") (result i64)
+                i64.const 9007199254740993)
+              (func (export "max") (result i64)
+                i64.const 9223372036854775807)
+              (func (export "min") (result i64)
+                i64.const -9223372036854775808)
+              (func (export "pair") (result i64 i64)
+                i64.const 9007199254740993
+                i64.const -9007199254740993))
+            """
+        )
+    ).exports
+
+    assert value_with_type(exports.above_2_53()) == (9007199254740993, int)
+    assert exports.above_2_53() == 2 ** 53 + 1
+    assert exports.max() == 2 ** 63 - 1
+    assert exports.min() == -2 ** 63
+    assert exports.pair() == (2 ** 53 + 1, -(2 ** 53 + 1))
+
+def test_call_i32_i32_with_int_enum():
+    class Color(IntEnum):
+        RED = 1
+        GREEN = 2
+
+    class Permission(IntFlag):
+        READ = 4
+        WRITE = 2
+
+    i32_i32 = instance().exports.i32_i32
+
+    assert value_with_type(i32_i32(Color.GREEN)) == (2, int)
+    assert value_with_type(i32_i32(Permission.READ | Permission.WRITE)) == (6, int)
+
 def test_call_f32_f32():
     assert value_with_type(instance().exports.f32_f32(7.)) == (7., float)
 
 def test_call_f64_f64():
     assert value_with_type(instance().exports.f64_f64(7.)) == (7., float)
 
+def test_call_f64_f64_with_decimal_and_fraction():
+    exports = instance().exports
+
+    assert value_with_type(exports.f64_f64(Decimal('1.5'))) == (1.5, float)
+    assert value_with_type(exports.f64_f64(Fraction(1, 4))) == (0.25, float)
+    assert value_with_type(exports.f32_f32(Decimal('2.5'))) == (2.5, float)
+
+def test_call_f64_f64_with_non_numeric_value():
+    with pytest.raises(TypeError):
+        instance().exports.f64_f64('1.5')
+
+    # An `int` is still not converted to a `float`.
+    with pytest.raises(TypeError):
+        instance().exports.f64_f64(1)
+
 def test_call_i32_i64_f32_f64_f64():
     assert round(instance().exports.i32_i64_f32_f64_f64(1, 2, 3.4, 5.6), 6) == (
         1 + 2 + 3.4 + 5.6
     )
 
+def test_partial():
+    i32_i64_f32_f64_f64 = instance().exports.i32_i64_f32_f64_f64
+    partial = i32_i64_f32_f64_f64.partial(1, 2)
+
+    assert partial.type.params == [Type.F32, Type.F64]
+    assert partial.type.results == [Type.F64]
+    assert partial.__name__ == 'i32_i64_f32_f64_f64'
+    assert round(partial(3.4, 5.6), 6) == 1 + 2 + 3.4 + 5.6
+    assert round(partial.partial(3.4)(5.6), 6) == 1 + 2 + 3.4 + 5.6
+    assert round(i32_i64_f32_f64_f64.partial()(1, 2, 3.4, 5.6), 6) == 1 + 2 + 3.4 + 5.6
+
+def test_partial_checks_the_arguments():
+    i32_i64_f32_f64_f64 = instance().exports.i32_i64_f32_f64_f64
+
+    with pytest.raises(TypeError) as context_manager:
+        i32_i64_f32_f64_f64.partial(1, 2, 3.4, 5.6, 7.8)
+
+    assert str(context_manager.value) == (
+        '`Function.partial` got 5 argument(s) to bind, but the function has only 4 parameter(s)'
+    )
+
+    with pytest.raises(TypeError):
+        i32_i64_f32_f64_f64.partial('1')
+
+    with pytest.raises(TypeError):
+        i32_i64_f32_f64_f64.partial(1, 2)(3.4, '5.6')
+
+def test_partial_rejects_references():
+    function = Function(Store(), lambda reference: None, FunctionType([Type.EXTERN_REF], []))
+
+    with pytest.raises(TypeError) as context_manager:
+        function.partial(Value.null())
+
+    assert str(context_manager.value) == (
+        '`Function.partial` cannot bind argument #0 of type `ExternRef`, only numbers can be bound'
+    )
+
+def test_partial_can_be_imported():
+    store = Store()
+    sum = Instance(
+        Module(
+            store,
+            '(module (func (export "sum") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))'
+        )
+    ).exports.sum
+
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "increment" (func $increment (param i32) (result i32)))
+          (func (export "run") (param i32) (result i32)
+            local.get 0
+            call $increment))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"increment": sum.partial(1)})
+
+    assert Instance(module, import_object).exports.run(41) == 42
+
 def test_call_bool_casted_to_i32():
     assert value_with_type(instance().exports.bool_casted_to_i32()) == (1, int)
 
@@ -108,3 +539,265 @@ def test_early_exit():
         assert 'oops' in str(err)
     else:
         assert False
+
+def test_host_trap():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (type $run_t (func (param i32 i32) (result i32)))
+          (type $abort_t (func (param) (result)))
+
+          (import "env" "abort" (func $abort (type $abort_t)))
+
+          (func $run (type $run_t) (param $x i32) (param $y i32) (result i32)
+            (call $abort)
+            (i32.add
+                local.get $x
+                local.get $y))
+
+          (export "run" (func $run)))
+        """
+    )
+
+    def abort():
+        raise HostTrap('aborted', 42)
+
+    import_object = ImportObject()
+    import_object.register(
+        "env",
+        {
+            "abort": Function(store, abort),
+        }
+    )
+    instance = Instance(module, import_object)
+
+    with pytest.raises(HostTrap) as context_manager:
+        instance.exports.run(1, 2)
+
+    exception = context_manager.value
+    assert isinstance(exception, RuntimeError)
+    assert exception.args == ('aborted', 42)
+
+def test_call_with_timeout_and_deadline():
+    import time
+
+    exports = instance().exports
+
+    assert exports.sum(1, 2, timeout=60) == 3
+    assert exports.sum(1, 2, deadline=time.monotonic() + 60) == 3
+    assert exports.sum(1, 2, timeout=None, deadline=None) == 3
+
+    with pytest.raises(wasmer.CallTimeoutError) as context_manager:
+        exports.sum(1, 2, deadline=time.monotonic() - 1)
+
+    assert isinstance(context_manager.value, TimeoutError)
+    assert str(context_manager.value) == 'The call has exceeded its deadline'
+
+    with pytest.raises(ValueError):
+        exports.sum(1, 2, timeout=float('nan'))
+
+def test_call_with_huge_timeout_and_deadline():
+    import time
+
+    exports = instance().exports
+
+    # Too far in the future to be represented: there is no deadline.
+    assert exports.sum(1, 2, timeout=1e20) == 3
+    assert exports.sum(1, 2, deadline=time.monotonic() + 1e20) == 3
+    assert exports.sum(1, 2, timeout=1e20, deadline=time.monotonic() + 60) == 3
+
+def test_call_with_timeout_stops_calling_the_host():
+    import time
+
+    ticks = []
+
+    def tick():
+        ticks.append(None)
+        time.sleep(0.05)
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "tick" (func $tick))
+          (func (export "run")
+            (local $i i32)
+            (loop $continue
+              call $tick
+              (local.set $i (i32.add (local.get $i) (i32.const 1)))
+              (br_if $continue (i32.lt_u (local.get $i) (i32.const 20))))))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"tick": Function(store, tick)})
+    run = Instance(module, import_object).exports.run
+
+    with pytest.raises(wasmer.CallTimeoutError):
+        run(timeout=0.12)
+
+    assert 1 <= len(ticks) < 20
+
+    del ticks[:]
+    run()
+
+    assert len(ticks) == 20
+
+def test_call_with_timeout_returns_the_results_of_a_completed_call():
+    import time
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "wait" (func $wait))
+          (func (export "answer") (result i32)
+            call $wait
+            i32.const 42))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"wait": Function(store, lambda: time.sleep(0.05))})
+    answer = Instance(module, import_object).exports.answer
+
+    # The deadline is exceeded during the last host call, but the
+    # call completes without calling the host again.
+    assert answer(timeout=0.01) == 42
+
+def test_raw_host_function():
+    store = Store()
+    calls = []
+
+    def forward(arguments):
+        calls.append([(argument.type, argument.value) for argument in arguments])
+
+        return [Value.i64(arguments[1].value * 2), 0.5]
+
+    function = Function(
+        store,
+        forward,
+        FunctionType([Type.I32, Type.I64], [Type.I64, Type.F32]),
+        raw=True
+    )
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "f" (func $f (param i32 i64) (result i64 f32)))
+          (func (export "run") (param i32 i64) (result i64 f32)
+            (call $f (local.get 0) (local.get 1))))
+        """
+    )
+    import_object = ImportObject()
+    import_object.register("env", {"f": function})
+
+    assert Instance(module, import_object).exports.run(1, 21) == (42, 0.5)
+    assert calls == [[(Type.I32, 1), (Type.I64, 21)]]
+
+def test_raw_host_function_with_invalid_results():
+    store = Store()
+    function_type = FunctionType([], [Type.I32])
+
+    wrong_type = Function(store, lambda arguments: Value.i64(1), function_type, raw=True)
+
+    with pytest.raises(TypeError) as context_manager:
+        host_function_instance(store, 'i32', wrong_type).exports.run()
+
+    assert str(context_manager.value) == 'Result #0 must be a value of type `I32`, got `I64`'
+
+    wrong_count = Function(store, lambda arguments: None, function_type, raw=True)
+
+    with pytest.raises(TypeError) as context_manager:
+        host_function_instance(store, 'i32', wrong_count).exports.run()
+
+    assert str(context_manager.value) == 'The raw function must return 1 result(s), got 0'
+
+def test_raw_host_function_requires_a_function_type():
+    with pytest.raises(ValueError):
+        Function(Store(), lambda arguments: None, raw=True)
+
+def numpy_like_scalar(name, value):
+    """Builds a fake NumPy scalar, i.e. a type from the `numpy`
+    module with an `item` method, since NumPy is an optional
+    dependency."""
+
+    return type(name, (), {'__module__': 'numpy', 'item': lambda self: value})()
+
+def host_function_instance(store, wat_type, function):
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "f" (func $f (result {type})))
+          (func (export "run") (result {type})
+            call $f))
+        """.format(type=wat_type)
+    )
+    import_object = ImportObject()
+    import_object.register("env", {"f": function})
+
+    return Instance(module, import_object)
+
+def test_host_function_returning_numpy_scalars():
+    store = Store()
+
+    for (wat_type, type, scalar, expected) in [
+        ('i32', Type.I32, numpy_like_scalar('int32', 5), 5),
+        ('i64', Type.I64, numpy_like_scalar('int64', -7), -7),
+        ('f32', Type.F32, numpy_like_scalar('float32', 1.5), 1.5),
+        ('f64', Type.F64, numpy_like_scalar('float64', 2.5), 2.5),
+    ]:
+        function = Function(store, lambda: scalar, FunctionType([], [type]))
+
+        assert host_function_instance(store, wat_type, function).exports.run() == expected
+
+def test_host_function_returning_real_numpy_scalar():
+    numpy = pytest.importorskip('numpy')
+    store = Store()
+
+    def f() -> int:
+        return numpy.int32(5)
+
+    assert host_function_instance(store, 'i32', Function(store, f)).exports.run() == 5
+
+def test_call_with_numpy_scalars():
+    exports = instance().exports
+
+    assert value_with_type(exports.i32_i32(numpy_like_scalar('int32', 7))) == (7, int)
+    assert value_with_type(exports.f64_f64(numpy_like_scalar('float64', 1.5))) == (1.5, float)
+
+    # A NumPy float is not converted to an integer.
+    with pytest.raises(TypeError):
+        exports.i32_i32(numpy_like_scalar('float64', 1.5))
+
+def test_call_with_non_numpy_object_having_an_item_method():
+    class NotNumpy:
+        def item(self):
+            return 7
+
+    with pytest.raises(TypeError):
+        instance().exports.i32_i32(NotNumpy())
+
+def test_function_references_are_not_supported_yet():
+    # Wasmer 1.0 compilers do not support the reference types
+    # proposal (`ref.func`, `funcref` results etc.), so a function
+    # returning a function reference cannot be compiled yet.
+    with pytest.raises(RuntimeError) as context_manager:
+        Module(
+            Store(),
+            """
+            (module
+              (func $answer (result i32) i32.const 42)
+              (elem declare func $answer)
+              (func (export "get") (result funcref)
+                ref.func $answer))
+            """
+        )
+
+    exception = context_manager.value
+    assert 'reference types support is not enabled' in str(exception)
@@ -0,0 +1,56 @@
+from __future__ import annotations
+from wasmer import Instance, Module, Store, Function, Type, ImportObject
+
+def test_constructor_with_postponed_annotations():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    function = Function(Store(), sum)
+    function_type = function.type
+
+    assert function_type.params == [Type.I32, Type.I32]
+    assert function_type.results == [Type.I32]
+
+def test_constructor_with_postponed_float_annotations():
+    def sum(x: float, y: float) -> float:
+        return x + y
+
+    function = Function(Store(), sum)
+    function_type = function.type
+
+    assert function_type.params == [Type.F32, Type.F32]
+    assert function_type.results == [Type.F32]
+
+def test_constructor_with_postponed_unresolvable_annotations():
+    def sum(x: i64, y: i64) -> i64:
+        return x + y
+
+    function = Function(Store(), sum)
+    function_type = function.type
+
+    assert function_type.params == [Type.I64, Type.I64]
+    assert function_type.results == [Type.I64]
+
+def test_call_with_postponed_annotations():
+    def sum(x: int, y: int) -> int:
+        return x + y
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "math" "sum" (func $sum (param i32 i32) (result i32)))
+          (func (export "add_one") (param i32) (result i32)
+            local.get 0
+            i32.const 1
+            call $sum))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("math", {"sum": Function(store, sum)})
+
+    instance = Instance(module, import_object)
+
+    assert instance.exports.add_one(41) == 42
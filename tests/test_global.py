@@ -63,6 +63,17 @@ def test_type():
     assert type.mutable == True
     assert str(type) == 'GlobalType(type: I32, mutable: true)'
 
+def test_value_type():
+    global_ = instance().exports.x
+
+    assert global_.value_type == Type.I32
+    assert global_.value_type == global_.type.type
+    assert Global(Store(), Value.f32(4.2)).value_type == Type.F32
+
+def test_content_type():
+    assert instance().exports.z.type.content_type == Type.I32
+    assert GlobalType(Type.F64, mutable=True).content_type == Type.F64
+
 def test_global_mutable():
     exports = instance().exports
 
@@ -108,3 +119,60 @@ def test_global_read_write_constant():
     assert str(exception) == (
         'The global variable is not mutable, cannot set a new value'
     )
+
+def test_int_and_float():
+    exports = instance().exports
+
+    assert (int(exports.z), float(exports.z)) == (42, 42.0)
+
+    exports.increment_x()
+
+    assert int(exports.x) == 1
+    assert int(exports.x) + int(exports.z) == 43
+
+def test_int_and_float_of_float_globals():
+    global_ = Global(Store(), Value.f64(4.5), mutable=True)
+
+    assert float(global_) == 4.5
+    assert int(global_) == 4
+
+    global_.value = -1.5
+
+    assert float(global_) == -1.5
+    assert int(global_) == -1
+
+def test_reset():
+    exports = instance().exports
+
+    exports.increment_x()
+    exports.increment_x()
+    exports.y.value = 153
+
+    assert exports.x.value == 2
+    assert exports.y.value == 153
+
+    exports.x.reset()
+    exports.y.reset()
+
+    assert exports.x.value == 0
+    assert exports.get_x() == 0
+    assert exports.y.value == 7
+
+def test_reset_host_global():
+    global_ = Global(Store(), Value.i32(42), mutable=True)
+    global_.value = 153
+    global_.reset()
+
+    assert global_.value == 42
+
+def test_reset_immutable():
+    with pytest.raises(RuntimeError) as context_manager:
+        instance().exports.z.reset()
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The global variable is not mutable, there is nothing to reset'
+    )
+
+def test_reset_doc():
+    assert Global.reset.__doc__.startswith('Restores a mutable global to its initial value')
@@ -1,4 +1,4 @@
-from wasmer import ImportObject, Store, Module, Instance, Function, Memory, MemoryType, Global, Value
+from wasmer import ImportObject, Store, Module, Instance, Function, Memory, MemoryType, Global, Value, FunctionType, Type
 import pytest
 
 def test_constructor():
@@ -38,6 +38,92 @@ def test_import_function():
 
     assert instance.exports.add_one(1) == 2
 
+def test_import_function_under_multiple_names():
+    calls = []
+
+    def sum(x: int, y: int) -> int:
+        calls.append((x, y))
+
+        return x + y
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "math" "sum" (func $sum (param i32 i32) (result i32)))
+          (import "math" "add" (func $add (param i32 i32) (result i32)))
+          (import "env" "plus" (func $plus (param i32 i32) (result i32)))
+          (func (export "run") (result i32)
+            (call $plus
+              (call $sum (i32.const 1) (i32.const 2))
+              (call $add (i32.const 3) (i32.const 4)))))
+        """
+    )
+
+    function = Function(store, sum)
+
+    import_object = ImportObject()
+    import_object.register("math", {"sum": function, "add": function})
+    import_object.register("env", {"plus": function})
+
+    assert Instance(module, import_object).exports.run() == 10
+    # All the imports call the same host function.
+    assert calls == [(1, 2), (3, 4), (3, 7)]
+
+def test_import_function_with_an_explicit_type():
+    def sum(x, y):
+        return x + y
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "math" "sum" (func $sum (param i64 i64) (result i64)))
+          (func (export "add_one") (param i64) (result i64)
+            local.get 0
+            i64.const 1
+            call $sum))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register(
+        "math",
+        {
+            "sum": (sum, FunctionType([Type.I64, Type.I64], [Type.I64]))
+        },
+        store=store
+    )
+
+    instance = Instance(module, import_object)
+
+    assert instance.exports.add_one(2 ** 40) == 2 ** 40 + 1
+
+def test_import_function_with_an_explicit_type_requires_a_store():
+    def sum(x, y):
+        return x + y
+
+    import_object = ImportObject()
+
+    with pytest.raises(TypeError) as context_manager:
+        import_object.register(
+            "math",
+            {
+                "sum": (sum, FunctionType([Type.I32, Type.I32], [Type.I32]))
+            }
+        )
+
+    exception = context_manager.value
+    assert str(exception) == 'A `Store` is required to register the `(callable, FunctionType)` entry `sum`'
+
+def test_import_invalid_tuple():
+    import_object = ImportObject()
+
+    with pytest.raises(TypeError):
+        import_object.register("math", {"sum": (1, 2)}, store=Store())
+
 def test_import_memory():
     store = Store()
     module = Module(
@@ -107,3 +193,155 @@ def test_import_global():
     assert instance.exports.read_g() == 153
     instance.exports.write_g(11)
     assert global_.value == 11
+
+def test_import_registered_as_none_not_required():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "answer" (func $answer (result i32)))
+          (func (export "run") (result i32)
+            call $answer))
+        """
+    )
+
+    def answer() -> int:
+        return 42
+
+    import_object = ImportObject()
+    import_object.register(
+        "env",
+        {
+            "answer": Function(store, answer),
+            "not_yet_implemented": None,
+        }
+    )
+
+    assert Instance(module, import_object).exports.run() == 42
+
+def test_import_registered_as_none_but_required():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "foo" (func))
+          (import "env" "bar" (func)))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"foo": None, "bar": None, "baz": None})
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Instance(module, import_object)
+
+    exception = context_manager.value
+    assert str(exception).endswith(
+        '(the module requires imports registered as `None`: `env`.`foo`, `env`.`bar`)'
+    )
+
+FALLBACK_MODULE = """
+(module
+  (import "env" "answer" (func $answer (result i32)))
+  (import "math" "sum" (func $sum (param i32 i32) (result i32)))
+  (func (export "run") (result i32)
+    (call $sum (call $answer) (i32.const 1))))
+"""
+
+def test_fallback():
+    store = Store()
+    module = Module(store, FALLBACK_MODULE)
+    requests = []
+
+    def fallback(namespace_name, name, function_type):
+        requests.append((namespace_name, name, function_type.params, function_type.results))
+
+        return Function(store, lambda x, y: x + y, function_type)
+
+    import_object = ImportObject()
+    import_object.register("env", {"answer": Function(store, lambda: 41, FunctionType([], [Type.I32]))})
+    import_object.set_fallback(fallback)
+
+    assert Instance(module, import_object).exports.run() == 42
+    # The registered imports are not given to the fallback.
+    assert requests == [("math", "sum", [Type.I32, Type.I32], [Type.I32])]
+
+def test_fallback_for_imports_registered_as_none():
+    store = Store()
+    module = Module(store, '(module (import "env" "answer" (func (result i32))) (export "answer" (func 0)))')
+
+    import_object = ImportObject()
+    import_object.register("env", {"answer": None})
+    import_object.set_fallback(lambda namespace_name, name, function_type: Function(store, lambda: 42, function_type))
+
+    # A re-exported host function cannot be called from Python, so only its type is checked.
+    assert Instance(module, import_object).exports.answer.type.results == [Type.I32]
+
+def test_fallback_declining():
+    store = Store()
+    module = Module(store, FALLBACK_MODULE)
+
+    def fallback(namespace_name, name, function_type):
+        if namespace_name == "math":
+            raise LookupError("no math")
+
+        return None
+
+    import_object = ImportObject()
+    import_object.set_fallback(fallback)
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Instance(module, import_object)
+
+    exception = context_manager.value
+    assert str(exception).endswith(
+        '(the fallback declined imports required by the module: '
+        '`env`.`answer` (returned `None`), `math`.`sum` (LookupError: no math))'
+    )
+
+def test_fallback_returning_a_non_function():
+    module = Module(Store(), FALLBACK_MODULE)
+
+    import_object = ImportObject()
+    import_object.set_fallback(lambda namespace_name, name, function_type: 42)
+
+    with pytest.raises(TypeError) as context_manager:
+        Instance(module, import_object)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The fallback must return a `Function` or `None` for the import `env`.`answer`, got `int`'
+    )
+
+def test_fallback_raising_a_base_exception():
+    module = Module(Store(), FALLBACK_MODULE)
+
+    def fallback(namespace_name, name, function_type):
+        raise KeyboardInterrupt()
+
+    import_object = ImportObject()
+    import_object.set_fallback(fallback)
+
+    # It does not decline the import, it is propagated.
+    with pytest.raises(KeyboardInterrupt):
+        Instance(module, import_object)
+
+def test_fallback_removed():
+    store = Store()
+    module = Module(store, FALLBACK_MODULE)
+
+    import_object = ImportObject()
+    import_object.set_fallback(lambda namespace_name, name, function_type: Function(store, lambda *arguments: 0, function_type))
+    import_object.set_fallback(None)
+
+    with pytest.raises(RuntimeError):
+        Instance(module, import_object)
+
+def test_fallback_not_callable():
+    with pytest.raises(TypeError) as context_manager:
+        ImportObject().set_fallback(42)
+
+    exception = context_manager.value
+    assert str(exception) == 'The fallback must be a callable'
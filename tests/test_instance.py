@@ -1,5 +1,5 @@
 import wasmer
-from wasmer import Instance, Module, Store, Exports, Function, Global, Table, Memory
+from wasmer import Instance, Module, Store, Exports, ExportKind, Function, Global, Table, Memory, ImportObject, Value
 import os
 import pytest
 
@@ -59,3 +59,454 @@ def test_export_does_not_exist():
 
     exception = context_manager.value
     assert str(exception) == 'Export `foo` does not exist.'
+
+def test_exports_repr():
+    instance = Instance(
+        Module(
+            Store(),
+            """
+            (module
+              (func (export "func") (param i32 i64))
+              (global (export "glob") i32 (i32.const 7))
+              (table (export "tab") 0 funcref)
+              (memory (export "mem") 1))
+            """
+        )
+    )
+
+    assert repr(instance.exports) == 'Exports([func, glob, tab, mem])'
+    assert repr(Instance(Module(Store(), '(module)')).exports) == 'Exports([])'
+
+def test_exports_counts():
+    counts = Instance(Module(Store(), TEST_BYTES)).exports.counts()
+
+    assert counts == {
+        ExportKind.FUNCTION: 10,
+        ExportKind.GLOBAL: 2,
+        ExportKind.MEMORY: 1,
+        ExportKind.TABLE: 0,
+    }
+    assert list(counts) == list(ExportKind)
+    assert all(isinstance(kind, ExportKind) for kind in counts)
+
+def test_exports_kind_of():
+    exports = Instance(Module(Store(), TEST_BYTES)).exports
+
+    assert exports.kind_of('sum') == ExportKind.FUNCTION
+    assert exports.kind_of('memory') == ExportKind.MEMORY
+    assert repr(exports.kind_of('sum')) == '<ExportKind.FUNCTION: 1>'
+    assert exports.kind_of('memory') is ExportKind.MEMORY
+
+def test_exports_kind_of_unknown_export():
+    exports = Instance(Module(Store(), TEST_BYTES)).exports
+
+    with pytest.raises(LookupError) as context_manager:
+        exports.kind_of('foo')
+
+    exception = context_manager.value
+    assert str(exception) == 'Export `foo` does not exist.'
+
+def test_exports_matching():
+    exports = Instance(Module(Store(), TEST_BYTES)).exports
+    functions = exports.matching('i32_')
+
+    assert len(functions) > 0
+    assert all(name.startswith('i32_') for name in functions)
+    assert all(isinstance(function, Function) for function in functions.values())
+    assert functions['i32_i32'] is exports.i32_i32
+
+def test_exports_matching_only_functions():
+    exports = Instance(Module(Store(), TEST_BYTES)).exports
+
+    assert 'memory' not in exports.matching('')
+    assert len(exports.matching('')) == exports.counts()[ExportKind.FUNCTION]
+    assert exports.matching('unknown_') == {}
+
+def test_exports_are_cached():
+    exports = Instance(Module(Store(), TEST_BYTES)).exports
+
+    assert exports.sum is exports.sum
+    assert exports.memory is exports.memory
+    assert {exports.sum: 'sum'}[exports.sum] == 'sum'
+
+ALLOCATOR_WAT = """
+(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 8))
+  (global $freed (export "freed") (mut i32) (i32.const -1))
+
+  (func $malloc (export "malloc") (param $size i32) (result i32)
+    (local $pointer i32)
+    global.get $next
+    local.set $pointer
+    global.get $next
+    local.get $size
+    i32.add
+    global.set $next
+    local.get $pointer)
+  (export "alloc" (func $malloc))
+
+  (func (export "free") (param i32)
+    local.get 0
+    global.set $freed)
+
+  (func (export "bad_malloc") (param i32)))
+"""
+
+def test_pass_string():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    uint8 = instance.exports.memory.uint8_view()
+
+    first = instance.pass_string('Hello')
+    second = instance.pass_string('café')
+
+    assert first == 8
+    assert second == 13
+    assert bytes(uint8[first:first + 5]) == b'Hello'
+    assert bytes(uint8[second:second + 5]) == 'café'.encode('utf-8')
+
+def test_pass_string_with_custom_allocator():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+
+    assert instance.pass_string('Hello', allocator='alloc') == 8
+    assert instance.pass_string('Hello', allocator='alloc', memory='memory') == 13
+
+def test_pass_string_with_missing_exports():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+
+    with pytest.raises(LookupError):
+        instance.pass_string('Hello', allocator='foo')
+
+    with pytest.raises(LookupError):
+        instance.pass_string('Hello', memory='malloc')
+
+def test_pass_string_with_invalid_allocator():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+
+    with pytest.raises(RuntimeError) as context_manager:
+        instance.pass_string('Hello', allocator='bad_malloc')
+
+    exception = context_manager.value
+    assert str(exception) == 'The allocator must return a single `i32` pointer, got `[]`'
+
+def test_pass_string_larger_than_memory_frees_it():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+
+    with pytest.raises(IndexError):
+        instance.pass_string('a' * 70000)
+
+    # The allocation is released.
+    assert instance.exports.freed.value == 8
+
+def test_free_string():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    pointer = instance.pass_string('Hello')
+
+    instance.free_string(pointer)
+
+    assert instance.exports.freed.value == pointer
+
+    with pytest.raises(LookupError):
+        instance.free_string(pointer, deallocator='dealloc')
+
+def test_find_allocator():
+    assert Instance(Module(Store(), ALLOCATOR_WAT)).exports.find_allocator() == ('malloc', 'free')
+    assert Instance(Module(Store(), TEST_BYTES)).exports.find_allocator() == None
+
+    # The signatures must be the expected ones.
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "malloc") (param i32))
+          (func (export "free") (param i32))
+          (func (export "allocate") (param i32) (result i32) i32.const 0)
+          (func (export "deallocate") (param i32)))
+        """
+    )
+
+    assert Instance(module).exports.find_allocator() == ('allocate', 'deallocate')
+
+def test_pass_string_with_found_allocator():
+    instance = Instance(
+        Module(
+            Store(),
+            """
+            (module
+              (memory (export "memory") 1)
+              (func (export "__wbindgen_malloc") (param i32) (result i32) i32.const 16)
+              (func (export "__wbindgen_free") (param i32)))
+            """
+        )
+    )
+
+    assert instance.pass_string('Hello') == 16
+
+    instance.free_string(16)
+
+def test_set_allocator():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    instance.set_allocator('alloc', 'free')
+
+    pointer = instance.pass_string('Hello')
+    assert pointer == 8
+
+    instance.free_string(pointer)
+    assert instance.exports.freed.value == pointer
+
+def test_set_allocator_with_missing_exports():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    instance.set_allocator('__wbindgen_malloc', '__wbindgen_free')
+
+    with pytest.raises(LookupError) as context_manager:
+        instance.pass_string('Hello')
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The instance has no function exported as `__wbindgen_malloc` to be used as the allocator; '
+        'set the allocator exports with `Instance.set_allocator`'
+    )
+
+    with pytest.raises(LookupError) as context_manager:
+        instance.free_string(8)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The instance has no function exported as `__wbindgen_free` to be used as the deallocator; '
+        'set the allocator exports with `Instance.set_allocator`'
+    )
+
+def test_fresh():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    instance.exports.memory.uint8_view()[0] = 42
+    instance.pass_string('Hello')
+
+    fresh_instance = instance.fresh()
+
+    assert isinstance(fresh_instance, Instance)
+    assert fresh_instance.exports.memory.uint8_view()[0] == 0
+    assert fresh_instance.pass_string('Hello') == 8
+
+    # Both instances do not share their mutable memory.
+    fresh_instance.exports.memory.uint8_view()[1] = 7
+    assert instance.exports.memory.uint8_view()[0:2] == [42, 0]
+    assert fresh_instance.exports.memory.uint8_view()[0:2] == [0, 7]
+
+def test_fresh_keeps_the_import_object_and_the_allocator():
+    store = Store()
+    import_object = ImportObject()
+    import_object.register('env', {'g': Global(store, Value.i32(7))})
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "g" (global i32))
+          (func (export "read_g") (result i32)
+            global.get 0))
+        """
+    )
+
+    assert Instance(module, import_object).fresh().exports.read_g() == 7
+
+    instance = Instance(Module(store, ALLOCATOR_WAT))
+    instance.set_allocator('alloc', 'free')
+
+    assert instance.fresh().pass_string('Hello') == 8
+
+def test_close():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    memory = instance.exports.memory
+
+    assert instance.close() == True
+    assert instance.close() == False
+
+    with pytest.raises(RuntimeError) as context_manager:
+        instance.exports
+
+    assert str(context_manager.value) == 'The instance is closed, it cannot be used after `Instance.close`'
+
+    with pytest.raises(RuntimeError):
+        instance.pass_string('Hello')
+
+    with pytest.raises(RuntimeError):
+        instance.fresh()
+
+    # Exports obtained before closing keep working.
+    assert memory.size == 1
+
+def test_close_releases_the_host_functions():
+    import gc, weakref
+
+    store = Store()
+    module = Module(store, '(module (import "env" "f" (func)) (func (export "g") call 0))')
+
+    def f():
+        pass
+
+    import_object = ImportObject()
+    import_object.register("env", {"f": Function(store, f)})
+
+    instance = Instance(module, import_object)
+    instance.exports.g()
+
+    reference = weakref.ref(f)
+    del f, import_object
+    gc.collect()
+
+    assert reference() is not None
+
+    instance.close()
+    gc.collect()
+
+    assert reference() is None
+
+def test_invoke():
+    instance = Instance(Module(Store(), TEST_BYTES))
+
+    assert instance.invoke('sum', 1, 2) == 3
+    assert instance.invoke('arity_0') == 42
+
+def test_invoke_unknown_export():
+    with pytest.raises(LookupError) as context_manager:
+        Instance(Module(Store(), TEST_BYTES)).invoke('foo')
+
+    exception = context_manager.value
+    assert str(exception) == 'Export `foo` does not exist.'
+
+def test_invoke_not_a_function():
+    with pytest.raises(TypeError) as context_manager:
+        Instance(Module(Store(), TEST_BYTES)).invoke('memory')
+
+    exception = context_manager.value
+    assert str(exception) == 'Export `memory` is not a function'
+
+def test_invoke_checks_the_arguments():
+    instance = Instance(Module(Store(), TEST_BYTES))
+
+    with pytest.raises(RuntimeError):
+        instance.invoke('sum', 1)
+
+    with pytest.raises(RuntimeError):
+        instance.invoke('sum', 1, 2, 3)
+
+    with pytest.raises(TypeError):
+        instance.invoke('sum', 1, 'two')
+
+ARRAY_MODULE = r"""
+(module
+  (memory (export "memory") 1)
+  (data (i32.const 16) "\01\00\00\00\02\00\00\00\03\00\00\00")
+  (func (export "numbers") (result i32 i32)
+    i32.const 16
+    i32.const 3)
+  (func (export "reversed_numbers") (result i32 i32)
+    i32.const 3
+    i32.const 16)
+  (func (export "numbers_from") (param $pointer i32) (result i32 i32)
+    local.get $pointer
+    i32.const 3)
+  (func (export "single") (result i32)
+    i32.const 16))
+"""
+
+def test_call_returning_array():
+    numpy = pytest.importorskip('numpy')
+    instance = Instance(Module(Store(), ARRAY_MODULE))
+
+    array = instance.call_returning_array('numbers', numpy.int32)
+
+    assert array.dtype == numpy.int32
+    assert array.tolist() == [1, 2, 3]
+
+    # The array shares the memory data.
+    instance.exports.memory.uint8_view(16)[0] = 42
+
+    assert array.tolist() == [42, 2, 3]
+
+def test_call_returning_array_with_arguments():
+    numpy = pytest.importorskip('numpy')
+    instance = Instance(Module(Store(), ARRAY_MODULE))
+
+    assert instance.call_returning_array('numbers_from', numpy.uint8, 17).tolist() == [0, 0, 0]
+    assert instance.call_returning_array('numbers_from', 'int16', 20).tolist() == [2, 0, 3]
+
+def test_call_returning_array_with_conventions():
+    numpy = pytest.importorskip('numpy')
+    instance = Instance(Module(Store(), ARRAY_MODULE))
+
+    array = instance.call_returning_array('reversed_numbers', numpy.int32, convention='length_pointer')
+
+    assert array.tolist() == [1, 2, 3]
+
+    array = instance.call_returning_array(
+        'numbers',
+        numpy.uint8,
+        convention=lambda results: (results[0], results[1] * 4),
+    )
+
+    assert array.tolist() == [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]
+
+def test_call_returning_array_out_of_bounds():
+    numpy = pytest.importorskip('numpy')
+    instance = Instance(Module(Store(), ARRAY_MODULE))
+
+    with pytest.raises(IndexError) as context_manager:
+        instance.call_returning_array('numbers', numpy.int32, convention=lambda results: (65530, 2))
+
+    exception = context_manager.value
+    assert str(exception) == 'Out of bound: The slice `65530:65538` is larger than the memory size 65536'
+
+def test_call_returning_array_with_unexpected_results():
+    instance = Instance(Module(Store(), ARRAY_MODULE))
+
+    with pytest.raises(TypeError) as context_manager:
+        instance.call_returning_array('single', 'int32')
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The function `single` must return 2 `i32` results for the `pointer_length` convention, got `16`'
+    )
+
+def test_call_returning_array_with_unknown_convention():
+    instance = Instance(Module(Store(), ARRAY_MODULE))
+
+    with pytest.raises(ValueError) as context_manager:
+        instance.call_returning_array('numbers', 'int32', convention='pointer')
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The convention must be `"pointer_length"`, `"length_pointer"` or a callable, got `pointer`'
+    )
+
+def test_last_call_stack_usage():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "probe" (func $probe))
+          (func $recurse (export "recurse") (param $depth i32)
+            (if (i32.eqz (local.get $depth))
+              (then call $probe)
+              (else (call $recurse (i32.sub (local.get $depth) (i32.const 1))))))
+          (func (export "nothing")))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register('env', {'probe': Function(store, lambda: None, wasmer.FunctionType([], []))})
+    instance = Instance(module, import_object)
+
+    assert instance.last_call_stack_usage == None
+
+    instance.exports.recurse(10)
+    shallow = instance.last_call_stack_usage
+
+    instance.exports.recurse(1000)
+    deep = instance.last_call_stack_usage
+
+    assert 0 < shallow < deep
+
+    instance.exports.nothing()
+
+    assert instance.last_call_stack_usage == None
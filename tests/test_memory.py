@@ -1,9 +1,10 @@
-from wasmer import Instance, Module, Store, Memory, MemoryType, Buffer, Uint8Array, Int8Array, Uint16Array, Int16Array, Uint32Array, Int32Array
+from wasmer import Instance, ImportObject, Module, Store, Memory, MemoryType, Buffer, Uint8Array, Int8Array, Uint16Array, Int16Array, Uint32Array, Int32Array, Type, memories_equal
 import ctypes
 import gc
 import inspect
 import os
 import pytest
+import struct
 
 here = os.path.dirname(os.path.realpath(__file__))
 TEST_BYTES = open(here + '/tests.wasm', 'rb').read()
@@ -230,6 +231,42 @@ def test_memory_grow():
     assert memory_length == 1179648
     assert memory_length - old_memory_length == 65536
 
+def test_memory_view_after_grow():
+    memory = instance().exports.memory
+    uint8 = memory.uint8_view()
+    int32 = memory.int32_view(offset=2)
+    uint8[0:4] = [1, 2, 3, 4]
+
+    old_data_size = memory.data_size
+    memory.grow(2)
+
+    # Views built before the growth see the whole grown memory.
+    assert uint8[0:4] == [1, 2, 3, 4]
+    assert len(uint8) == old_data_size + 2 * 65536
+
+    uint8[old_data_size + 65536] = 42
+    int32[old_data_size // 4] = 7
+
+    assert memory.uint8_view()[old_data_size + 65536] == 42
+    assert memory.int32_view()[old_data_size // 4 + 2] == 7
+
+def test_memory_grow_with_report():
+    memory = instance().exports.memory
+    data_ptr = memory.grow(0, report=True).data_ptr
+
+    growth = memory.grow(1, report=True)
+
+    assert growth.previous_pages == 17
+    assert growth.previous_data_ptr == data_ptr
+    assert isinstance(growth.data_ptr, int)
+    assert memory.size == 18
+
+    previous_pages, previous_data_ptr, data_ptr = memory.grow(1, report=True)
+
+    assert previous_pages == 18
+    assert previous_data_ptr == growth.data_ptr
+    assert type(growth) == type(memory.grow(0, report=True))
+
 def test_memory_grow_too_much():
     with pytest.raises(RuntimeError) as context_manager:
         instance().exports.memory.grow(100000)
@@ -239,6 +276,89 @@ def test_memory_grow_too_much():
         'The memory could not grow: current size 17 pages, requested increase: 100000 pages'
     )
 
+def test_memory_try_grow():
+    memory = instance().exports.memory
+
+    assert memory.try_grow(2) == 17
+    assert memory.size == 19
+
+def test_memory_try_grow_too_much():
+    memory = instance().exports.memory
+
+    assert memory.try_grow(100000) == None
+    assert memory.size == 17
+
+def test_memory_try_grow_with_exported_buffer():
+    memory = instance().exports.memory
+    view = memoryview(memory.buffer)
+
+    assert memory.try_grow(1) == None
+    assert memory.size == 17
+
+    view.release()
+
+    assert memory.try_grow(1) == 17
+
+def test_host_memory_maximum_is_enforced():
+    store = Store()
+    memory = Memory(store, MemoryType(1, maximum=2, shared=False))
+
+    assert memory.type.maximum == 2
+
+    memory.grow(1)
+
+    assert memory.size == 2
+
+    with pytest.raises(RuntimeError):
+        memory.grow(1)
+
+    assert memory.size == 2
+
+def test_host_memory_maximum_is_enforced_on_guest_grow():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "memory" (memory 1))
+          (func (export "grow") (param i32) (result i32)
+            local.get 0
+            memory.grow))
+        """
+    )
+    memory = Memory(store, MemoryType(1, maximum=2, shared=False))
+
+    import_object = ImportObject()
+    import_object.register("env", {"memory": memory})
+
+    instance = Instance(module, import_object)
+
+    # `memory.grow` returns the previous size in pages, or -1 on failure.
+    assert instance.exports.grow(1) == 1
+    assert instance.exports.grow(1) == -1
+    assert memory.size == 2
+
+def test_memory_grow_to_fit():
+    memory = instance().exports.memory
+
+    assert memory.grow_to_fit(1114112) == 1114112
+    assert memory.size == 17
+
+    assert memory.grow_to_fit(1114113) == 1179648
+    assert memory.size == 18
+
+    assert memory.grow_to_fit(0) == 1179648
+    assert memory.size == 18
+
+def test_memory_grow_to_fit_too_much():
+    with pytest.raises(ValueError) as context_manager:
+        instance().exports.memory.grow_to_fit(2 ** 64 - 1)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Cannot grow the memory to fit 18446744073709551615 bytes; it exceeds the maximum number of pages'
+    )
+
 def test_memory_buffer_memoryview():
     memory = instance().exports.memory
 
@@ -262,6 +382,483 @@ def test_memory_buffer_memoryview():
     assert memory_view.contiguous == True
     assert memory_view[0:3].tolist() == [1, 2, 3]
 
+def test_memory_slice():
+    memory = instance().exports.memory
+
+    int8 = memory.int8_view()
+    int8[7] = 1
+    int8[8] = 2
+    int8[9] = 3
+
+    slice = memory.slice(7, 3)
+
+    assert isinstance(slice, memoryview)
+    assert slice.readonly == True
+    assert slice.nbytes == 3
+    assert slice.tolist() == [1, 2, 3]
+
+    int8[8] = 42
+
+    assert slice.tolist() == [1, 42, 3]
+
+def test_memory_slice_is_readonly():
+    slice = instance().exports.memory.slice(0, 3)
+
+    with pytest.raises(TypeError):
+        slice[0] = 42
+
+def test_memory_slice_out_of_range():
+    with pytest.raises(IndexError) as context_manager:
+        instance().exports.memory.slice(1114110, 3)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `1114110:1114113` is larger than the memory size 1114112'
+    )
+
+def test_memory_view_with_limit():
+    memory = instance().exports.memory
+    window = memory.uint8_view(offset=16, limit=4)
+
+    assert len(window) == 4
+    assert len(memory.uint8_view(offset=1114110, limit=4)) == 2
+
+    window[0:4] = [1, 2, 3, 4]
+
+    assert window[0:4] == [1, 2, 3, 4]
+    assert window[0:100] == [1, 2, 3, 4]
+    assert window[3] == 4
+    assert memory.uint8_view()[15:21] == [0, 1, 2, 3, 4, 0]
+
+def test_memory_view_with_limit_out_of_range():
+    window = instance().exports.memory.uint8_view(offset=16, limit=4)
+
+    with pytest.raises(IndexError) as context_manager:
+        window[4]
+
+    exception = context_manager.value
+    assert str(exception) == 'Out of bound: Absolute index 20 is beyond the view limit (offset 16, limit 4)'
+
+    with pytest.raises(IndexError):
+        window[4:6]
+
+    with pytest.raises(IndexError):
+        window[4] = 1
+
+    with pytest.raises(IndexError):
+        window[2:6] = [1, 2, 3, 4]
+
+    # Nothing has been written.
+    assert window[0:4] == [0, 0, 0, 0]
+
+def test_memory_view_with_empty_limit():
+    window = instance().exports.memory.int32_view(limit=0)
+
+    assert len(window) == 0
+
+    with pytest.raises(IndexError):
+        window[0]
+
+def test_memory_write_array():
+    memory = instance().exports.memory
+
+    assert memory.write_array(7, [1, -1, 256], Type.I32) == 12
+    assert memory.uint8_view(7)[0:12] == [1, 0, 0, 0, 255, 255, 255, 255, 0, 1, 0, 0]
+
+def test_memory_write_array_of_any_iterable():
+    memory = instance().exports.memory
+
+    assert memory.write_array(0, (x for x in range(3)), Type.I64) == 24
+    assert memory.read_array(0, 3, Type.I64) == [0, 1, 2]
+
+def test_memory_read_array():
+    memory = instance().exports.memory
+
+    for (type, values) in [
+        (Type.I32, [-2147483648, 0, 2147483647]),
+        (Type.I64, [-9223372036854775808, 0, 9223372036854775807]),
+        (Type.F32, [1.5, -2.25, 0.0]),
+        (Type.F64, [1.1, -2.2, 3.3]),
+        (Type.V128, [1, 2 ** 128 - 1]),
+    ]:
+        memory.write_array(16, values, type)
+
+        assert memory.read_array(16, len(values), type) == values
+
+def test_memory_array_endianness():
+    memory = instance().exports.memory
+    values = [0x01020304, 0x7fffffff, 1]
+
+    assert memory.write_array(0, values, Type.I32, endianness='little') == 12
+    assert memory.uint8_view(0)[0:4] == [4, 3, 2, 1]
+    assert memory.read_array(0, 3, Type.I32) == values
+    assert memory.read_array(0, 3, Type.I32, endianness='big') == [0x04030201, -129, 0x01000000]
+
+    assert memory.write_array(0, values, Type.I32, endianness='big') == 12
+    assert memory.uint8_view(0)[0:12] == [1, 2, 3, 4, 127, 255, 255, 255, 0, 0, 0, 1]
+    assert memory.read_array(0, 3, Type.I32, endianness='big') == values
+    assert memory.read_u32(0) == 0x04030201
+
+def test_memory_array_endianness_of_floats():
+    memory = instance().exports.memory
+
+    memory.write_array(0, [1.5, -2.25], Type.F64, endianness='big')
+
+    assert memory.read_array(0, 2, Type.F64, endianness='big') == [1.5, -2.25]
+    assert bytes(memory.uint8_view(0)[0:16]) == struct.pack('>dd', 1.5, -2.25)
+
+def test_memory_array_of_floats_layout():
+    memory = instance().exports.memory
+
+    assert memory.write_array(0, [1.5, 2.5], Type.F32) == 8
+    assert bytes(memory.uint8_view(0)[0:8]) == struct.pack('<ff', 1.5, 2.5)
+
+    assert memory.write_array(0, [1.5, 2.5], Type.F64) == 16
+    assert bytes(memory.uint8_view(0)[0:16]) == struct.pack('<dd', 1.5, 2.5)
+    assert memory.read_array(0, 2, Type.F64) == [1.5, 2.5]
+
+    # A `f32` is rounded to the single precision.
+    memory.write_array(0, [0.1], Type.F32)
+
+    assert memory.read_array(0, 1, Type.F32) == [struct.unpack('<f', struct.pack('<f', 0.1))[0]]
+
+def test_memory_array_of_floats_out_of_range():
+    memory = instance().exports.memory
+
+    with pytest.raises(IndexError) as context_manager:
+        memory.write_array(1114104, [1.0, 2.0], Type.F64)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `1114104:1114120` is larger than the memory size 1114112'
+    )
+
+    with pytest.raises(IndexError):
+        memory.read_array(1114108, 2, Type.F32)
+
+def test_memory_unpack():
+    memory = instance().exports.memory
+    memory.write(16, struct.pack('<iHxxd', -1, 7, 2.5))
+
+    assert memory.unpack('iHxxd', 16) == (-1, 7, 2.5)
+
+def test_memory_pack():
+    memory = instance().exports.memory
+
+    assert memory.pack('iHxxd', 16, -1, 7, 2.5) == 16
+    assert bytes(memory.uint8_view(16)[0:16]) == struct.pack('<iHxxd', -1, 7, 2.5)
+
+def test_memory_pack_with_a_byte_order():
+    memory = instance().exports.memory
+
+    assert memory.pack('>I', 0, 0x01020304) == 4
+    assert memory.uint8_view(0)[0:4] == [1, 2, 3, 4]
+    assert memory.unpack('>I', 0) == (0x01020304,)
+    assert memory.unpack('I', 0) == (0x04030201,)
+
+def test_memory_pack_invalid_format():
+    memory = instance().exports.memory
+
+    with pytest.raises(struct.error):
+        memory.unpack('Z', 0)
+
+    with pytest.raises(struct.error):
+        memory.pack('I', 0, 'not a number')
+
+def test_memory_pack_out_of_range():
+    memory = instance().exports.memory
+
+    with pytest.raises(IndexError) as context_manager:
+        memory.unpack('II', 1114108)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `1114108:1114116` is larger than the memory size 1114112'
+    )
+
+    with pytest.raises(IndexError):
+        memory.pack('Q', 1114108, 1)
+
+def test_memory_array_invalid_endianness():
+    memory = instance().exports.memory
+
+    with pytest.raises(ValueError) as context_manager:
+        memory.write_array(0, [1], Type.I32, endianness='middle')
+
+    exception = context_manager.value
+    assert str(exception) == 'The endianness must be `"little"` or `"big"`; given `middle`'
+
+    with pytest.raises(ValueError):
+        memory.read_array(0, 1, Type.I32, endianness='native')
+
+def test_memory_array_out_of_range():
+    memory = instance().exports.memory
+
+    with pytest.raises(IndexError) as context_manager:
+        memory.write_array(1114108, [1, 2], Type.I32)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `1114108:1114116` is larger than the memory size 1114112'
+    )
+
+    with pytest.raises(IndexError):
+        memory.read_array(1114108, 2, Type.I32)
+
+def test_memory_array_of_reference_type():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.memory.read_array(0, 1, Type.FUNC_REF)
+
+    exception = context_manager.value
+    assert str(exception) == 'Arrays of `FuncRef` values are not supported'
+
+def test_memory_as_array():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+    memory.write_array(8, [1, -2, 3], Type.I32)
+
+    view = memory.as_array('i', 8, 3)
+
+    assert isinstance(view, memoryview)
+    assert view.tolist() == [1, -2, 3]
+
+    view[2] = 42
+
+    assert memory.read_i32(16) == 42
+
+    memory.write_array(8, [7], Type.I32)
+
+    assert view[0] == 7
+    assert len(memory.as_array('d')) == 65536 // 8
+    assert memory.as_array('B', 65534).tolist() == [0, 0]
+
+    with pytest.raises(BufferError):
+        memory.grow(1)
+
+    view.release()
+
+    assert memory.grow(1) == 1
+
+def test_memory_as_array_copy():
+    import array
+    import warnings
+
+    memory = Memory(Store(), MemoryType(1, shared=False))
+
+    with warnings.catch_warnings(record=True) as caught_warnings:
+        warnings.simplefilter('always')
+        copy = memory.as_array('u', 0, 2)
+
+    assert isinstance(copy, array.array)
+    assert len(copy) == 2
+    assert [warning.category for warning in caught_warnings] == [RuntimeWarning]
+
+def test_memory_as_array_invalid():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+
+    with pytest.raises(ValueError):
+        memory.as_array('z')
+
+    with pytest.raises(IndexError):
+        memory.as_array('q', 65528, 2)
+
+def test_memory_write_string():
+    memory = instance().exports.memory
+
+    assert memory.write_string(4, 'Wasmer') == 6
+    assert bytes(memory.uint8_view(4)[0:6]) == b'Wasmer'
+
+def test_memory_write_string_with_encoding():
+    memory = instance().exports.memory
+
+    for encoding in ['utf-8', 'latin-1', 'utf-16-le']:
+        expected = 'façade'.encode(encoding)
+
+        assert memory.write_string(0, 'façade', encoding=encoding) == len(expected)
+        assert bytes(memory.uint8_view()[0:len(expected)]) == expected
+
+def test_memory_write_string_with_encoding_error():
+    memory = instance().exports.memory
+
+    with pytest.raises(UnicodeEncodeError):
+        memory.write_string(0, '🐍', encoding='latin-1')
+
+def test_memory_write_string_out_of_range():
+    with pytest.raises(IndexError):
+        instance().exports.memory.write_string(1114110, 'abc')
+
+def test_memory_read_c_string():
+    memory = instance().exports.memory
+    memory.write_string(0, 'café\0Wasmer\0')
+
+    assert memory.read_c_string(0) == 'café'
+    assert memory.read_c_string(6) == 'Wasmer'
+    assert memory.read_c_string(12) == ''
+
+def test_memory_read_c_string_with_max_len():
+    memory = instance().exports.memory
+    memory.write_string(0, 'Wasmer\0')
+
+    assert memory.read_c_string(0, max_len=6) == 'Wasmer'
+    assert memory.read_c_string(0, max_len=100) == 'Wasmer'
+
+    with pytest.raises(ValueError) as context_manager:
+        memory.read_c_string(0, max_len=5)
+
+    exception = context_manager.value
+    assert str(exception) == 'No NUL terminator found in the string at offset 0 (read up to 6)'
+
+def test_memory_read_c_string_without_terminator():
+    memory = instance().exports.memory
+    uint8 = memory.uint8_view()
+    uint8[1114110] = 1
+    uint8[1114111] = 1
+
+    with pytest.raises(ValueError):
+        memory.read_c_string(1114110)
+
+    with pytest.raises(IndexError):
+        memory.read_c_string(1114113)
+
+def test_memory_read_c_string_invalid_utf8():
+    memory = instance().exports.memory
+    memory.uint8_view()[0:2] = [0xff, 0]
+
+    with pytest.raises(UnicodeDecodeError):
+        memory.read_c_string(0)
+
+def test_memory_read_and_write():
+    memory = instance().exports.memory
+
+    assert memory.write(8, b'Wasmer') == 6
+    assert memory.read(8, 6) == b'Wasmer'
+    assert memory.read(8, 0) == b''
+
+def test_memory_read_and_write_out_of_bounds_raise():
+    memory = instance().exports.memory
+
+    for policy in [None, 'raise']:
+        with pytest.raises(IndexError) as context_manager:
+            memory.read(1114110, 4, out_of_bounds=policy)
+
+        exception = context_manager.value
+        assert str(exception) == (
+            'Out of bound: The slice `1114110:1114114` is larger than the memory size 1114112'
+        )
+
+        with pytest.raises(IndexError):
+            memory.write(1114110, b'abcd', out_of_bounds=policy)
+
+        assert memory.read(1114110, 2) == b'\0\0'
+
+def test_memory_read_and_write_out_of_bounds_clamp():
+    memory = instance().exports.memory
+
+    assert memory.write(1114110, b'abcd', out_of_bounds='clamp') == 2
+    assert memory.read(1114110, 4, out_of_bounds='clamp') == b'ab'
+    assert memory.write(1114200, b'abcd', out_of_bounds='clamp') == 0
+    assert memory.read(1114200, 4, out_of_bounds='clamp') == b''
+
+def test_memory_read_and_write_out_of_bounds_callback():
+    memory = instance().exports.memory
+    calls = []
+
+    def on_out_of_bounds(offset, length, memory_size):
+        calls.append((offset, length, memory_size))
+
+        return 'handled'
+
+    assert memory.read(1114110, 4, out_of_bounds=on_out_of_bounds) == 'handled'
+    assert memory.write(1114111, b'abc', out_of_bounds=on_out_of_bounds) == 'handled'
+    assert memory.read(0, 4, out_of_bounds=on_out_of_bounds) == b'\0\0\0\0'
+    assert calls == [(1114110, 4, 1114112), (1114111, 3, 1114112)]
+    assert memory.read(1114110, 2) == b'\0\0'
+
+def test_memory_read_and_write_invalid_out_of_bounds_policy():
+    with pytest.raises(ValueError) as context_manager:
+        instance().exports.memory.read(0, 1, out_of_bounds='ignore')
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The out-of-bounds policy must be `"raise"`, `"clamp"`, or a callable; given `ignore`'
+    )
+
+def test_memory_compare():
+    memory = instance().exports.memory
+    memory.uint8_view()[10:13] = [1, 2, 3]
+
+    assert memory.compare(10, bytes([1, 2, 3]))
+    assert memory.compare(11, bytes([2, 3]))
+    assert memory.compare(10, b'')
+    assert not memory.compare(10, bytes([1, 2, 4]))
+
+def test_memory_compare_out_of_range():
+    with pytest.raises(IndexError) as context_manager:
+        instance().exports.memory.compare(1114110, b'abc')
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `1114110:1114113` is larger than the memory size 1114112'
+    )
+
+def test_memory_snapshot_and_restore():
+    memory = instance().exports.memory
+    uint8 = memory.uint8_view()
+
+    uint8[7] = 42
+    snapshot = memory.snapshot()
+
+    assert isinstance(snapshot, bytes)
+    assert len(snapshot) == 1114112
+    assert snapshot[7] == 42
+
+    uint8[7] = 153
+    uint8[8] = 1
+    memory.restore(snapshot)
+
+    assert uint8[7] == 42
+    assert uint8[8] == 0
+
+def test_memory_restore_grows_the_memory():
+    store = Store()
+    memory = Memory(store, MemoryType(2, shared=False))
+    memory.uint8_view()[65536] = 42
+
+    snapshot = memory.snapshot()
+
+    other_memory = Memory(store, MemoryType(1, shared=False))
+    other_memory.restore(snapshot)
+
+    assert other_memory.size == 2
+    assert other_memory.uint8_view()[65536] == 42
+
+def test_memory_restore_size_mismatch():
+    memory = instance().exports.memory
+    snapshot = memory.snapshot()
+    memory.grow(1)
+
+    with pytest.raises(ValueError) as context_manager:
+        memory.restore(snapshot)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The snapshot size (1114112 bytes) does not match the memory size (1179648 bytes)'
+    )
+
+def test_memory_restore_partial_page_snapshot():
+    store = Store()
+    memory = Memory(store, MemoryType(1, shared=False))
+
+    with pytest.raises(ValueError) as context_manager:
+        memory.restore(bytes(65536 + 1))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The snapshot size (65537 bytes) is not a multiple of the page size (65536 bytes)'
+    )
+
+    # The memory has not grown.
+    assert memory.size == 1
+
 def test_memory_buffer_bytearray():
     memory = instance().exports.memory
 
@@ -323,3 +920,241 @@ def test_memory_buffer_supports_keeps_object_alive():
     val = bytes([42] * 1024)
     for i in range(len(view) // 1024):
         view[i * 1024:(i + 1) * 1024] = val
+
+
+def test_memory_grow_with_exported_buffer():
+    store = Store()
+    memory = Memory(store, MemoryType(1, shared=False))
+    view = memoryview(memory.buffer)
+
+    with pytest.raises(BufferError) as context_manager:
+        memory.grow(1)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The memory cannot grow while 1 buffer export(s) are outstanding '
+        '(e.g. a `memoryview` or a NumPy array); release them first'
+    )
+
+    with pytest.raises(BufferError):
+        memory.grow_to_fit(memory.data_size + 1)
+
+    assert memory.size == 1
+
+    view.release()
+
+    memory.grow(1)
+    assert memory.size == 2
+
+
+def test_memory_grow_with_exported_buffer_from_another_memory_object():
+    memory = instance().exports.memory
+    view = memoryview(Memory(Store(), MemoryType(1, shared=False)).buffer)
+
+    # An export over another memory does not prevent the growth.
+    memory.grow(1)
+
+    view.release()
+
+
+def test_memory_grow_after_buffer_export_is_collected():
+    store = Store()
+    memory = Memory(store, MemoryType(1, shared=False))
+    views = [memoryview(memory.buffer), memoryview(memory.slice(0, 8))]
+
+    with pytest.raises(BufferError):
+        memory.grow(1)
+
+    del views
+    gc.collect()
+
+    memory.grow(1)
+    assert memory.size == 2
+
+
+def test_memory_to_bytes():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+    memory.write(0, b'Wasmer')
+
+    data = memory.to_bytes()
+
+    assert isinstance(data, bytes)
+    assert len(data) == memory.data_size
+    assert data[0:6] == b'Wasmer'
+
+    # It is a copy.
+    memory.write(0, b'wasmer')
+    assert data[0:6] == b'Wasmer'
+
+
+def test_memory_to_bytes_with_range():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+    memory.write(8, b'Wasmer')
+
+    assert memory.to_bytes(8, 6) == b'Wasmer'
+    assert memory.to_bytes(8, 0) == b''
+    assert memory.to_bytes(65534) == b'\x00\x00'
+    assert memory.to_bytes(65536) == b''
+
+
+def test_memory_to_bytes_out_of_range():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+
+    with pytest.raises(IndexError) as context_manager:
+        memory.to_bytes(65530, 7)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `65530:65537` is larger than the memory size 65536'
+    )
+
+    with pytest.raises(IndexError):
+        memory.to_bytes(65537)
+
+
+def test_memory_read_signed_and_unsigned_integers():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+    memory.write(0, b'\xff\xff\xff\xff')
+
+    assert memory.read_i32(0) == -1
+    assert memory.read_u32(0) == 4294967295
+
+    memory.write(8, b'\xff' * 8)
+
+    assert memory.read_i64(8) == -1
+    assert memory.read_u64(8) == 18446744073709551615
+
+    memory.write(16, (0x80000000).to_bytes(4, 'little'))
+
+    assert memory.read_i32(16) == -2147483648
+    assert memory.read_u32(16) == 2147483648
+
+    memory.write(20, (42).to_bytes(4, 'little'))
+
+    assert memory.read_i32(20) == 42
+    assert memory.read_u32(20) == 42
+
+
+def test_memory_read_integers_out_of_range():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+
+    assert memory.read_u32(65532) == 0
+
+    with pytest.raises(IndexError) as context_manager:
+        memory.read_i32(65533)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Out of bound: The slice `65533:65537` is larger than the memory size 65536'
+    )
+
+    with pytest.raises(IndexError):
+        memory.read_u64(65529)
+
+
+def test_memory_grow_counts_outstanding_buffer_exports():
+    memory = Memory(Store(), MemoryType(1, shared=False))
+    buffer = memory.buffer
+    first = memoryview(buffer)
+    second = memoryview(buffer)
+
+    with pytest.raises(BufferError) as context_manager:
+        memory.grow(1)
+
+    assert str(context_manager.value).startswith(
+        'The memory cannot grow while 2 buffer export(s) are outstanding'
+    )
+
+    first.release()
+
+    with pytest.raises(BufferError):
+        memory.grow(1)
+
+    second.release()
+
+    memory.grow(1)
+    assert memory.size == 2
+
+def test_memories_equal():
+    store = Store()
+    a = Memory(store, MemoryType(1, shared=False))
+    b = Memory(store, MemoryType(1, shared=False))
+
+    assert memories_equal(a, b)
+    assert memories_equal(a, a)
+
+    a.uint8_view()[65535] = 1
+
+    assert not memories_equal(a, b)
+
+    b.uint8_view()[65535] = 1
+
+    assert memories_equal(a, b)
+
+def test_memories_equal_with_different_sizes():
+    store = Store()
+    a = Memory(store, MemoryType(1, shared=False))
+    b = Memory(store, MemoryType(1, shared=False))
+
+    b.grow(1)
+
+    assert not memories_equal(a, b)
+
+    a.grow(1)
+
+    assert memories_equal(a, b)
+
+def test_memories_equal_with_instances():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory (export "memory") 1)
+          (func (export "write") (param i32 i32)
+            local.get 0
+            local.get 1
+            i32.store))
+        """
+    )
+    a = Instance(module).exports
+    b = Instance(module).exports
+
+    a.write(8, 42)
+    b.write(8, 42)
+
+    assert memories_equal(a.memory, b.memory)
+
+    b.write(12, 1)
+
+    assert not memories_equal(a.memory, b.memory)
+
+def test_memories_equal_with_a_non_memory():
+    with pytest.raises(TypeError):
+        memories_equal(Memory(Store(), MemoryType(1, shared=False)), b'')
+
+
+def test_memory_grow_instruction_with_exported_buffer():
+    instance = Instance(
+        Module(
+            Store(),
+            """
+            (module
+              (memory (export "memory") 1)
+              (func (export "grow") (param i32) (result i32)
+                local.get 0
+                memory.grow))
+            """
+        )
+    )
+    memory = instance.exports.memory
+    view = memoryview(memory.buffer)
+
+    # The instruction fails, as if the memory had reached its maximum.
+    assert instance.exports.grow(1) == -1
+    assert memory.size == 1
+    assert len(view) == memory.data_size
+
+    view.release()
+
+    assert instance.exports.grow(1) == 1
+    assert memory.size == 2
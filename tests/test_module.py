@@ -1,5 +1,5 @@
 import wasmer
-from wasmer import Store, Module, ExportType, ImportType, FunctionType, MemoryType, GlobalType, TableType, Type
+from wasmer import Store, Module, ExportType, ImportType, FunctionType, MemoryType, GlobalType, TableType, Type, Instance
 from enum import IntEnum
 import os
 import pytest
@@ -8,6 +8,36 @@ here = os.path.dirname(os.path.realpath(__file__))
 TEST_BYTES = open(here + '/tests.wasm', 'rb').read()
 INVALID_TEST_BYTES = open(here + '/invalid.wasm', 'rb').read()
 
+def trap_message(module):
+    with pytest.raises(RuntimeError) as context_manager:
+        Instance(module).exports.run()
+
+    return str(context_manager.value)
+
+def test_names():
+    store = Store()
+    wat = """
+    (module
+      (func $inner unreachable)
+      (func $run (export "run") call $inner))
+    """
+    bytes = wasmer.wat2wasm(wat)
+    stripped_bytes = wasmer.wat2wasm('(module (func unreachable) (func (export "run") call 0))')
+
+    assert 'at inner' in trap_message(Module(store, wat))
+    assert 'at <unnamed>' in trap_message(Module(store, stripped_bytes))
+
+    message = trap_message(Module(store, stripped_bytes, names={0: 'inner', 1: 'run', 7: 'unknown'}))
+
+    assert 'at inner' in message
+    assert 'at run' in message
+
+    # The given names take precedence over the names of the module.
+    assert 'at renamed' in trap_message(Module(store, bytes, names={0: 'renamed'}))
+
+    with pytest.raises(TypeError):
+        Module(store, bytes, names={'inner': 0})
+
 def test_validate():
     assert Module.validate(Store(), TEST_BYTES)
 
@@ -20,10 +50,65 @@ def test_compile_bytes():
 def test_compile_wat():
     assert isinstance(Module(Store(), '(module)'), Module)
 
+def test_compile_file_like_object():
+    with open(here + '/tests.wasm', 'rb') as file:
+        assert isinstance(Module(Store(), file), Module)
+
+def test_compile_file_like_object_from_memory():
+    import io
+
+    assert isinstance(Module(Store(), io.BytesIO(TEST_BYTES)), Module)
+
+def test_compile_file_like_object_not_returning_bytes():
+    import io
+
+    with pytest.raises(TypeError) as context_manager:
+        Module(Store(), io.StringIO('(module)'))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        '`Module` expects the `read` method of the file-like object to return bytes, got `str`'
+    )
+
+def test_compile_file_like_object_failing_to_read():
+    class File:
+        def read(self):
+            raise IOError('cannot read')
+
+    with pytest.raises(IOError) as context_manager:
+        Module(Store(), File())
+
+    exception = context_manager.value
+    assert str(exception) == 'cannot read'
+
+def test_compile_unsupported_type():
+    with pytest.raises(TypeError) as context_manager:
+        Module(Store(), 42)
+
+    exception = context_manager.value
+    assert str(exception) == '`Module` accepts Wasm bytes, a WAT string, or a file-like object'
+
 def test_failed_to_compile():
     with pytest.raises(RuntimeError) as context_manager:
         Module(Store(), INVALID_TEST_BYTES)
 
+def test_failed_to_compile_duplicate_export_names():
+    # Duplicate export names are rejected by the validator, so the
+    # last one cannot silently win.
+    with pytest.raises(RuntimeError) as context_manager:
+        Module(
+            Store(),
+            """
+            (module
+              (func $f)
+              (export "f" (func $f))
+              (export "f" (func $f)))
+            """
+        )
+
+    exception = context_manager.value
+    assert 'duplicate export name' in str(exception)
+
 def test_name_some():
     assert Module(Store(), '(module $moduleName)').name == 'moduleName'
 
@@ -35,6 +120,93 @@ def test_name_set():
     module.name = 'hello'
     assert module.name == 'hello'
 
+def test_name_set_after_instantiation():
+    module = Module(Store(), '(module $moduleName)')
+    Instance(module)
+
+    module.name = 'hello'
+    assert module.name == 'hello'
+
+def test_clone():
+    module = Module(Store(), '(module $moduleName (func (export "sum") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))')
+    clone = module.clone()
+
+    assert isinstance(clone, Module)
+    assert clone is not module
+    assert clone.name == 'moduleName'
+    assert [export.name for export in clone.exports] == ['sum']
+    assert Instance(clone).exports.sum(1, 2) == Instance(module).exports.sum(1, 2) == 3
+
+def test_clone_has_an_independent_name():
+    module = Module(Store(), '(module $moduleName)')
+    first = module.clone()
+    second = module.clone()
+
+    first.name = 'first'
+    second.name = 'second'
+
+    assert module.name == 'moduleName'
+    assert first.name == 'first'
+    assert second.name == 'second'
+
+    module.name = 'renamed'
+
+    assert first.name == 'first'
+    assert module.clone().name == 'renamed'
+
+def test_required_features_of_an_mvp_module():
+    assert Module(Store(), TEST_BYTES).required_features == set()
+
+def test_required_features():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory 1)
+          (func (export "fill") (param i32 i32 i32)
+            local.get 0
+            local.get 1
+            local.get 2
+            memory.fill)
+          (func (export "swap") (param i32 i64) (result i64 i32)
+            local.get 1
+            local.get 0))
+        """
+    )
+
+    assert module.required_features == {'bulk_memory', 'multi_value'}
+
+def test_required_features_of_blocks_and_segments():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory 1)
+          (data "hello")
+          (func (export "pair") (result i32 i32)
+            (block (result i32 i32)
+              i32.const 1
+              i32.const 2))
+          (func (export "drop")
+            data.drop 0))
+        """
+    )
+
+    assert module.required_features == {'bulk_memory', 'multi_value'}
+
+def test_required_features_are_cached():
+    module = Module(Store(), TEST_BYTES)
+
+    assert module.required_features == set()
+    assert module.required_features == set()
+    assert module.clone().required_features == set()
+
+def test_required_features_of_a_deserialized_module():
+    store = Store()
+    serialized_module = Module(store, '(module)').serialize()
+
+    assert Module.deserialize(store, serialized_module).required_features == None
+
 def test_exports():
     exports = Module(
         Store(),
@@ -71,6 +243,66 @@ def test_exports():
     assert exports[3].type.maximum == None
     assert exports[3].type.shared == False
 
+def test_exports_dict():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "function") (param i32 i64))
+          (global (export "global") i32 (i32.const 7))
+          (memory (export "memory") 1))
+        """
+    )
+    exports = module.exports_dict()
+
+    assert list(exports.keys()) == ['function', 'global', 'memory']
+    assert all(isinstance(export, ExportType) for export in exports.values())
+    assert exports['function'].name == 'function'
+    assert exports['function'].type.params == [Type.I32, Type.I64]
+    assert exports['global'].type.mutable == False
+    assert exports['memory'].type.minimum == 1
+    assert Module(Store(), '(module)').exports_dict() == {}
+
+def test_exports_function_body():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "imported" (func $imported))
+          (func $f (export "function") (param i32 i32) (result i32)
+            (local i32 i64 i64)
+            local.get 0
+            local.get 1
+            i32.add)
+          (func (export "empty"))
+          (export "imported" (func $imported))
+          (memory (export "memory") 1))
+        """
+    )
+    exports = {export.name: export for export in module.exports}
+
+    # 5 bytes for the locals, and 6 bytes for
+    # `local.get 0 local.get 1 i32.add end`.
+    assert exports['function'].function_body == (3, 5 + 6)
+    assert exports['function'].function_body.local_count == 3
+    assert exports['function'].function_body.body_size == 11
+    assert exports['empty'].function_body == (0, 2)
+    assert exports['imported'].function_body == None
+    assert exports['memory'].function_body == None
+
+def test_exports_function_body_and_required_features():
+    module = Module(Store(), '(module (func (export "f") (result i32 i64) i32.const 1 i64.const 2))')
+
+    assert module.exports[0].function_body == (0, 6)
+    assert module.required_features == {'multi_value'}
+    assert module.exports[0].function_body == (0, 6)
+
+def test_exports_function_body_of_a_deserialized_module():
+    store = Store()
+    serialized_module = Module(store, '(module (func (export "f")))').serialize()
+
+    assert Module.deserialize(store, serialized_module).exports[0].function_body == None
+
 def test_imports():
     imports = Module(
         Store(),
@@ -111,12 +343,320 @@ def test_imports():
     assert imports[3].type.maximum == 4
     assert imports[3].type.shared == False
 
+def test_rename_import():
+    bytes = wasmer.wat2wasm(
+        """
+        (module
+          (import "env" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+          (import "env" "memory" (memory 1))
+          (func (export "run")))
+        """
+    )
+    bytes = wasmer.rename_import(bytes, 'env', 'fd_write', 'wasi_snapshot_preview1', 'fd_write')
+    module = Module(Store(), bytes)
+
+    assert [(import_.module, import_.name) for import_ in module.imports] == [
+        ('wasi_snapshot_preview1', 'fd_write'),
+        ('env', 'memory'),
+    ]
+    assert module.imports[0].type.params == [Type.I32, Type.I32, Type.I32, Type.I32]
+    assert module.exports[0].name == 'run'
+
+def test_rename_import_unknown_import():
+    bytes = wasmer.wat2wasm('(module (import "env" "f" (func)))')
+
+    with pytest.raises(LookupError) as context_manager:
+        wasmer.rename_import(bytes, 'env', 'g', 'host', 'g')
+
+    assert str(context_manager.value) == 'The module has no import named `g` in the `env` namespace'
+
+    with pytest.raises(RuntimeError):
+        wasmer.rename_import(b'\x00asm', 'env', 'f', 'host', 'f')
+
+def test_imports_by_namespace():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "ns" "function" (func))
+          (import "other_ns" "global" (global f32))
+          (import "ns" "memory" (memory 3 4)))
+        """
+    )
+    imports = module.imports_by_namespace()
+
+    assert list(imports.keys()) == ["ns", "other_ns"]
+
+    assert len(imports["ns"]) == 2
+    assert isinstance(imports["ns"][0], ImportType)
+    assert imports["ns"][0].name == "function"
+    assert isinstance(imports["ns"][0].type, FunctionType)
+    assert imports["ns"][1].name == "memory"
+    assert isinstance(imports["ns"][1].type, MemoryType)
+
+    assert len(imports["other_ns"]) == 1
+    assert imports["other_ns"][0].module == "other_ns"
+    assert imports["other_ns"][0].name == "global"
+
+    assert len(module.imports) == 3
+
+def test_imports_by_namespace_without_imports():
+    assert Module(Store(), '(module)').imports_by_namespace() == {}
+
 def test_custom_section():
     module = Module(Store(), open(here + '/custom_sections.wasm', 'rb').read())
     assert module.custom_sections('easter_egg') == [b'Wasmer']
     assert module.custom_sections('hello') == [b'World!']
     assert module.custom_sections('foo') == []
 
+def leb128(value):
+    result = b''
+
+    while True:
+        byte = value & 0x7f
+        value >>= 7
+
+        if value == 0:
+            return result + bytes([byte])
+
+        result += bytes([byte | 0x80])
+
+def name(string):
+    return leb128(len(string)) + string.encode()
+
+def module_with_producers(fields):
+    payload = leb128(len(fields))
+
+    for (field, values) in fields:
+        payload += name(field) + leb128(len(values))
+
+        for (tool, version) in values:
+            payload += name(tool) + name(version)
+
+    section = name('producers') + payload
+
+    return b'\x00asm\x01\x00\x00\x00' + b'\x00' + leb128(len(section)) + section
+
+def test_producers():
+    module = Module(
+        Store(),
+        module_with_producers([
+            ('language', [('Rust', ''), ('C', '')]),
+            ('processed-by', [('rustc', '1.49.0'), ('wasm-opt', '97')]),
+            ('sdk', [('Emscripten', '2.0.10')]),
+        ])
+    )
+
+    assert module.producers == {
+        'language': {'Rust': '', 'C': ''},
+        'processed-by': {'rustc': '1.49.0', 'wasm-opt': '97'},
+        'sdk': {'Emscripten': '2.0.10'},
+    }
+
+def test_producers_empty():
+    assert Module(Store(), module_with_producers([])).producers == {}
+
+def test_no_producers():
+    assert Module(Store(), open(here + '/custom_sections.wasm', 'rb').read()).producers == None
+
+def test_start_function():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "f" (func))
+          (import "env" "g" (func))
+          (func $init)
+          (start $init))
+        """
+    )
+
+    assert module.start_function == {'index': 2, 'name': 'init'}
+
+def test_start_function_named_by_its_export():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func)
+          (func (export "main"))
+          (start 1))
+        """
+    )
+
+    assert module.start_function == {'index': 1, 'name': 'main'}
+
+def test_start_function_without_name():
+    module = Module(Store(), '(module (func) (start 0))')
+
+    assert module.start_function == {'index': 0, 'name': None}
+
+def test_no_start_function():
+    assert Module(Store(), TEST_BYTES).start_function == None
+
+def test_memory_count():
+    store = Store()
+
+    assert Module(store, TEST_BYTES).memory_count == 1
+    assert Module(store, '(module)').memory_count == 0
+    # Defined but not exported.
+    assert Module(store, '(module (memory 1))').memory_count == 1
+    assert Module(store, '(module (import "env" "memory" (memory 1)))').memory_count == 1
+
+def test_table_count():
+    store = Store()
+
+    assert Module(store, '(module)').table_count == 0
+    assert Module(store, '(module (table 2 funcref))').table_count == 1
+    assert Module(store, '(module (import "env" "table" (table 1 funcref)))').table_count == 1
+
+def test_initial_memory_data():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "base" (global $base i32))
+          (memory 1)
+          (data (i32.const 0) "abc")
+          (data (global.get $base) "def")
+          (data (i32.const 1) "XY"))
+        """
+    )
+
+    assert module.initial_memory_data() == [
+        {'memory_index': 0, 'offset': 0, 'global_index': None, 'data': b'abc'},
+        {'memory_index': 0, 'offset': 0, 'global_index': 0, 'data': b'def'},
+        {'memory_index': 0, 'offset': 1, 'global_index': None, 'data': b'XY'},
+    ]
+
+def test_initial_memory_data_without_data():
+    store = Store()
+
+    assert Module(store, '(module)').initial_memory_data() == []
+    assert Module(store, '(module (memory 1))').initial_memory_data() == []
+
+def test_initial_memory_data_image():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory 2)
+          (data (i32.const 0) "abc")
+          (data (i32.const 1) "XY")
+          (data (i32.const 65536) "z"))
+        """
+    )
+    image = module.initial_memory_data(image=True)
+
+    assert len(image) == 2 * 65536
+    # The segments are written in order.
+    assert image[0:4] == b'aXY\0'
+    assert image[65535:65538] == b'\0z\0'
+    assert image.count(0) == 2 * 65536 - 4
+
+def test_initial_memory_data_image_of_a_too_large_memory():
+    module = Module(Store(), '(module (memory 65536) (data (i32.const 0) "abc"))')
+
+    with pytest.raises(ValueError) as context_manager:
+        module.initial_memory_data(image=True)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The memory has a minimum size of 4294967296 byte(s), the image is limited to 67108864 byte(s)'
+    )
+
+    assert module.initial_memory_data()[0]['data'] == b'abc'
+
+def test_initial_memory_data_image_of_a_deserialized_module():
+    store = Store()
+    serialized_module = Module(store, '(module (memory 1) (data (i32.const 2) "hi"))').serialize()
+    image = Module.deserialize(store, serialized_module).initial_memory_data(image=True)
+
+    assert image[0:4] == b'\0\0hi'
+
+def test_initial_memory_data_image_with_a_global_offset():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "base" (global $base i32))
+          (memory 1)
+          (data (global.get $base) "def"))
+        """
+    )
+
+    with pytest.raises(ValueError) as context_manager:
+        module.initial_memory_data(image=True)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'A data segment is placed at the value of the global at index 0, the image cannot be computed without instantiating the module'
+    )
+
+def test_initial_memory_data_image_out_of_bounds():
+    module = Module(Store(), '(module (memory 1) (data (i32.const 65535) "ab"))')
+
+    with pytest.raises(ValueError) as context_manager:
+        module.initial_memory_data(image=True)
+
+    exception = context_manager.value
+    assert str(exception) == 'A data segment ends at the offset 65537, out of the 65536 byte(s) of the memory'
+
+def test_initial_memory_data_image_without_memory():
+    with pytest.raises(LookupError) as context_manager:
+        Module(Store(), '(module)').initial_memory_data(image=True)
+
+    exception = context_manager.value
+    assert str(exception) == 'The module has no memory at index 0'
+
+def test_generate_stub():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "sum") (param i32 i64) (result f64)
+            f64.const 0)
+          (global (export "glob") i32 (i32.const 7))
+          (table (export "tab") 0 funcref)
+          (memory (export "mem") 1))
+        """
+    )
+    stub = module.generate_stub()
+
+    compile(stub, 'exports.pyi', 'exec')
+    assert stub.endswith(
+        'class Exports:\n'
+        '    def sum(self, arg0: int, arg1: int) -> float: ...\n'
+        '    glob: Global\n'
+        '    tab: Table\n'
+        '    mem: Memory\n'
+    )
+
+def test_generate_stub_with_invalid_identifiers():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "foo-bar"))
+          (func (export "class")))
+        """
+    )
+    stub = module.generate_stub()
+
+    compile(stub, 'exports.pyi', 'exec')
+    assert stub.endswith(
+        'class Exports:\n'
+        '    # `foo-bar` is not a valid Python identifier.\n'
+        '    # `class` is not a valid Python identifier.\n'
+        '    ...\n'
+    )
+
+def test_generate_stub_without_exports():
+    stub = Module(Store(), '(module)').generate_stub()
+
+    compile(stub, 'exports.pyi', 'exec')
+    assert stub.endswith('class Exports:\n    ...\n')
+
 def test_serialize():
     assert type(Module(Store(), "(module)").serialize()) == bytes
 
@@ -140,3 +680,47 @@ def test_deserialize():
     assert isinstance(exports[0].type, FunctionType)
     assert exports[0].type.params == [Type.I32, Type.I64]
     assert exports[0].type.results == []
+
+def test_serialize_to_file_and_load_dylib(tmp_path):
+    store = Store()
+    path = tmp_path / 'module.artifact'
+
+    Module(
+        store,
+        """
+        (module
+          (func (export "sum") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.add))
+        """
+    ).serialize_to_file(path)
+
+    assert path.exists()
+
+    module = Module.load_dylib(store, str(path))
+
+    assert Instance(module).exports.sum(1, 2) == 3
+    assert isinstance(Module.load_dylib(store, path), Module)
+    assert isinstance(Module.load_dylib(store, os.fsencode(path)), Module)
+
+def test_serialize_to_file_with_bytes_path(tmp_path):
+    path = tmp_path / 'module.artifact'
+
+    Module(Store(), '(module)').serialize_to_file(os.fsencode(path))
+
+    assert path.exists()
+
+def test_serialize_to_file_with_invalid_path():
+    with pytest.raises(TypeError):
+        Module(Store(), '(module)').serialize_to_file(42)
+
+def test_load_dylib_invalid_artifact(tmp_path):
+    path = tmp_path / 'module.artifact'
+    path.write_bytes(b'not an artifact')
+
+    with pytest.raises(RuntimeError):
+        Module.load_dylib(Store(), path)
+
+    with pytest.raises(RuntimeError):
+        Module.load_dylib(Store(), tmp_path / 'missing.artifact')
@@ -1,4 +1,5 @@
-from wasmer import engine, Store, Module, Instance
+from wasmer import engine, Store, Module, Instance, ImportObject, Function, CallInterruptedError
+from decimal import Decimal
 import itertools
 import os
 import platform
@@ -13,6 +14,58 @@ def test_store_defaults():
     assert store.engine_name == 'jit'
     assert store.compiler_name == 'cranelift'
 
+def test_store_default():
+    store = Store.default()
+
+    assert isinstance(store, Store)
+    assert store.engine_name == 'jit'
+    assert store.compiler_name == 'cranelift'
+
+def test_store_with_canonicalized_nans():
+    import wasmer_compiler_cranelift
+
+    wat = """
+    (module
+      (func (export "div") (param f32 f32) (result i32)
+        local.get 0
+        local.get 1
+        f32.div
+        i32.reinterpret_f32))
+    """
+
+    store = Store(engine.JIT(wasmer_compiler_cranelift.Compiler, canonicalize_nans=True))
+    instance = Instance(Module(store, wat))
+
+    # The canonical NaN for `f32` is `0x7fc00000`.
+    assert instance.exports.div(0.0, 0.0) == 0x7fc00000
+
+def test_store_with_canonicalized_nans_without_a_compiler():
+    for engine_class in (engine.JIT, engine.Native):
+        with pytest.raises(ValueError) as context_manager:
+            engine_class(canonicalize_nans=True)
+
+        exception = context_manager.value
+        assert str(exception) == '`canonicalize_nans` requires a compiler'
+
+def test_store_with_a_compiler_without_options():
+    import wasmer_compiler_cranelift
+
+    # A compiler package predating the compiler options.
+    class Compiler:
+        @staticmethod
+        def into_opaque_compiler():
+            return wasmer_compiler_cranelift.Compiler.into_opaque_compiler()
+
+    store = Store(engine.JIT(Compiler))
+
+    assert store.compiler_name == 'cranelift'
+
+    with pytest.raises(RuntimeError) as context_manager:
+        engine.JIT(Compiler, canonicalize_nans=True)
+
+    exception = context_manager.value
+    assert str(exception) == 'The compiler does not support `canonicalize_nans`, please upgrade it'
+
 #@pytest.mark.skipif(platform.system() == 'Windows', reason='Wasmer (`master`) has some troubles with JIT on Windows for the moment.')
 #def test_store_with_various_engines_and_compilers():
 #    import wasmer_compiler_llvm
@@ -49,3 +102,309 @@ def test_store_defaults():
 #            instance = Instance(module)
 #
 #            assert instance.exports.sum(1, 2)
+
+def test_store_as_context_manager():
+    with Store() as store:
+        assert isinstance(store, Store)
+
+        instance = Instance(Module(store, TEST_BYTES))
+
+    # Objects built from the store keep working.
+    assert instance.exports.sum(1, 2) == 3
+    assert store.engine_name == 'jit'
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Module(store, '(module)')
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The store is closed, it cannot be used after `Store.close` or exiting its `with` block'
+    )
+
+def test_store_is_closed_when_an_exception_is_raised():
+    with pytest.raises(ValueError):
+        with Store() as store:
+            raise ValueError('oops')
+
+    with pytest.raises(RuntimeError):
+        Module.validate(store, b'\0asm')
+
+def test_store_close():
+    store = Store()
+    instance = Instance(Module(store, TEST_BYTES))
+
+    assert store.close() == True
+    assert store.close() == False
+
+    with store:
+        pass
+
+    assert store.close() == False
+    assert instance.exports.sum(1, 2) == 3
+
+    with pytest.raises(RuntimeError):
+        Module(store, '(module)')
+
+def test_store_max_instances():
+    import gc
+
+    store = Store(max_instances=2)
+    module = Module(store, TEST_BYTES)
+    first = Instance(module)
+    second = Instance(module)
+
+    assert store.live_instance_count == 2
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Instance(module)
+
+    assert str(context_manager.value) == (
+        'The store cannot have more than 2 live instance(s); close some with `Instance.close`'
+    )
+    assert store.live_instance_count == 2
+
+    first.close()
+    first.close()
+
+    assert store.live_instance_count == 1
+
+    third = Instance(module)
+    del second, third
+    gc.collect()
+
+    assert store.live_instance_count == 0
+    assert Store().live_instance_count == 0
+
+STRICT_MARSHALING_WAT = """
+(module
+  (import "env" "flag" (func $flag (result i32)))
+  (func (export "identity") (param i32 f64) (result i32)
+    local.get 0)
+  (func (export "flag") (result i32)
+    call $flag))
+"""
+
+def strict_marshaling_instance(store, flag):
+    import_object = ImportObject()
+    import_object.register('env', {'flag': Function(store, flag)})
+
+    return Instance(Module(store, STRICT_MARSHALING_WAT), import_object)
+
+def test_store_strict_marshaling():
+    assert Store().strict_marshaling == False
+    assert Store(strict_marshaling=True).strict_marshaling == True
+    assert Store(engine.JIT(), strict_marshaling=True).strict_marshaling == True
+
+def test_store_without_strict_marshaling_accepts_bool():
+    def flag() -> int:
+        return True
+
+    exports = strict_marshaling_instance(Store(), flag).exports
+
+    assert exports.identity(True, 1.0) == 1
+    assert exports.identity(1, Decimal('1.5')) == 1
+    assert exports.flag() == 1
+
+def test_store_with_strict_marshaling_refuses_bool_arguments():
+    def flag() -> int:
+        return 1
+
+    exports = strict_marshaling_instance(Store(strict_marshaling=True), flag).exports
+
+    assert exports.identity(7, 1.0) == 7
+
+    with pytest.raises(TypeError) as context_manager:
+        exports.identity(True, 1.0)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Argument #0 (`True`) must be of type `int` for the `I32` parameter, got `bool` (strict marshaling)'
+    )
+
+    # An `int` is never converted to a `float`.
+    with pytest.raises(TypeError):
+        exports.identity(7, 1)
+
+    with pytest.raises(TypeError) as context_manager:
+        exports.identity(7, Decimal('1.5'))
+
+    exception = context_manager.value
+    assert str(exception) == (
+        "Argument #1 (`1.5`) must be of type `float` for the `F64` parameter, got `Decimal` (strict marshaling)"
+    )
+
+def test_store_with_strict_marshaling_refuses_bool_results():
+    def flag() -> int:
+        return True
+
+    exports = strict_marshaling_instance(Store(strict_marshaling=True), flag).exports
+
+    with pytest.raises(TypeError) as context_manager:
+        exports.flag()
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'Result #0 (`True`) must be of type `int` for the `I32` result, got `bool` (strict marshaling)'
+    )
+
+def test_fresh_instance_keeps_strict_marshaling():
+    def flag() -> int:
+        return 1
+
+    instance = strict_marshaling_instance(Store(strict_marshaling=True), flag)
+
+    with pytest.raises(TypeError):
+        instance.fresh().exports.identity(True, 1.0)
+
+def artifacts(store, count):
+    return [
+        Module(store, '(module (func (export "f{}")))'.format(nth)).serialize()
+        for nth in range(count)
+    ]
+
+def test_store_module_cache_is_disabled_by_default():
+    store = Store()
+    artifact, = artifacts(store, 1)
+
+    Module.deserialize(store, artifact)
+
+    assert store.cached_module_count == 0
+
+def test_store_module_cache():
+    store = Store(cache_size=2)
+    first, second, third = artifacts(store, 3)
+
+    assert Module.deserialize(store, first).exports[0].name == 'f0'
+    assert Module.deserialize(store, first).exports[0].name == 'f0'
+    assert store.cached_module_count == 1
+
+    assert Module.deserialize(store, second).exports[0].name == 'f1'
+    assert store.cached_module_count == 2
+
+    # The least recently used module is evicted.
+    assert Module.deserialize(store, third).exports[0].name == 'f2'
+    assert store.cached_module_count == 2
+
+    assert Module.deserialize(store, first).exports[0].name == 'f0'
+    assert Instance(Module.deserialize(store, first)).exports.f0() == None
+
+    store.clear_module_cache()
+
+    assert store.cached_module_count == 0
+    assert Module.deserialize(store, second).exports[0].name == 'f1'
+
+TICKING_MODULE = """
+(module
+  (import "env" "tick" (func $tick))
+  (func (export "run") (param i32)
+    (loop $loop
+      call $tick
+      local.get 0
+      i32.const 1
+      i32.sub
+      local.tee 0
+      br_if $loop)))
+"""
+
+def ticking_instance(store, tick):
+    import_object = ImportObject()
+    import_object.register("env", {"tick": Function(store, tick)})
+
+    return Instance(Module(store, TICKING_MODULE), import_object)
+
+def test_store_interrupt_a_call_on_another_thread():
+    import threading
+
+    store = Store()
+    started = threading.Event()
+    ticks = []
+
+    def tick():
+        ticks.append(None)
+        started.set()
+
+    errors = []
+
+    # The store can be shared with another thread, but not the
+    # module and the instance.
+    def run():
+        instance = ticking_instance(store, tick)
+
+        try:
+            instance.exports.run(-1)
+        except Exception as error:
+            errors.append(error)
+
+        # The next calls are not interrupted.
+        ticks.clear()
+        instance.exports.run(3)
+
+    thread = threading.Thread(target=run)
+    thread.start()
+    started.wait()
+
+    store.interrupt()
+    thread.join()
+
+    exception, = errors
+    assert isinstance(exception, CallInterruptedError)
+    assert isinstance(exception, InterruptedError)
+    assert str(exception) == 'The call has been interrupted by `Store.interrupt`'
+    assert len(ticks) == 3
+
+def test_store_interrupt_from_a_host_function():
+    store = Store()
+    ticks = []
+
+    def tick():
+        ticks.append(None)
+
+        if len(ticks) == 2:
+            store.interrupt()
+
+    instance = ticking_instance(store, tick)
+
+    with pytest.raises(CallInterruptedError):
+        instance.exports.run(10)
+
+    assert len(ticks) == 2
+
+def test_store_interrupt_without_calls_in_progress():
+    store = Store()
+    ticks = []
+    instance = ticking_instance(store, lambda: ticks.append(None))
+
+    store.interrupt()
+    instance.exports.run(3)
+
+    assert len(ticks) == 3
+
+def test_store_interrupt_does_not_interrupt_other_stores():
+    store = Store()
+    other_store = Store()
+    ticks = []
+
+    def tick():
+        ticks.append(None)
+        other_store.interrupt()
+
+    ticking_instance(store, tick).exports.run(3)
+
+    assert len(ticks) == 3
+
+def test_store_interrupt_after_the_last_host_call():
+    store = Store()
+    ticks = []
+
+    def tick():
+        ticks.append(None)
+
+        if len(ticks) == 3:
+            store.interrupt()
+
+    instance = ticking_instance(store, tick)
+
+    # The call completes without calling the host again, so its
+    # results are not dropped.
+    assert instance.exports.run(3) == None
+    assert len(ticks) == 3
@@ -1,4 +1,4 @@
-from wasmer import Instance, Module, Store, Table, TableType, Type
+from wasmer import Instance, Module, Store, Table, TableType, Type, Value, ImportObject
 import pytest
 
 TEST_BYTES = """
@@ -22,3 +22,116 @@ def test_type():
 
 def test_size():
     assert instance().exports.table.size == 0
+
+def test_grow():
+    instance = Instance(
+        Module(
+            Store(),
+            """
+            (module
+              (type $answer_t (func (result i32)))
+              (table (export "table") 1 3 funcref)
+              (func (export "answer") (type $answer_t)
+                i32.const 42)
+              (func (export "call") (param i32) (result i32)
+                local.get 0
+                call_indirect (type $answer_t)))
+            """
+        )
+    )
+    table = instance.exports.table
+
+    assert table.grow(1, instance.exports.answer) == 1
+    assert table.size == 2
+    assert instance.exports.call(1) == 42
+
+    assert table.grow(1, None) == 2
+    assert table.size == 3
+
+    with pytest.raises(RuntimeError):
+        instance.exports.call(2)
+
+def test_grow_too_much():
+    table = Instance(Module(Store(), '(module (table (export "table") 0 1 funcref))')).exports.table
+
+    with pytest.raises(RuntimeError):
+        table.grow(2, None)
+
+def test_constructor_with_null():
+    table = Table(Store(), TableType(Type.FUNC_REF, 2, None), Value.null())
+
+    assert table.size == 2
+
+def functions(store):
+    return Instance(
+        Module(
+            store,
+            """
+            (module
+              (func (export "one") (result i32) i32.const 1)
+              (func (export "two") (result i32) i32.const 2))
+            """
+        )
+    ).exports
+
+def dispatch_instance(store, table):
+    module = Module(
+        store,
+        """
+        (module
+          (type $t (func (result i32)))
+          (import "env" "table" (table 3 funcref))
+          (func (export "call") (param i32) (result i32)
+            local.get 0
+            call_indirect (type $t)))
+        """
+    )
+
+    import_object = ImportObject()
+    import_object.register("env", {"table": table})
+
+    return Instance(module, import_object)
+
+def test_constructor_with_a_function():
+    store = Store()
+    table = Table(store, TableType(Type.FUNC_REF, 3, 3), functions(store).one)
+    instance = dispatch_instance(store, table)
+
+    assert [instance.exports.call(index) for index in range(3)] == [1, 1, 1]
+
+def test_constructor_with_none():
+    store = Store()
+    table = Table(store, TableType(Type.FUNC_REF, 3, 3), None)
+    instance = dispatch_instance(store, table)
+
+    with pytest.raises(RuntimeError):
+        instance.exports.call(0)
+
+def test_constructor_without_initial_value():
+    assert Table(Store(), TableType(Type.FUNC_REF, 2, None)).size == 2
+
+def test_constructor_with_a_list():
+    store = Store()
+    exports = functions(store)
+    table = Table(store, TableType(Type.FUNC_REF, 3, 3), [exports.one, None, exports.two])
+    instance = dispatch_instance(store, table)
+
+    assert instance.exports.call(0) == 1
+    assert instance.exports.call(2) == 2
+
+    with pytest.raises(RuntimeError):
+        instance.exports.call(1)
+
+def test_constructor_with_a_list_of_the_wrong_length():
+    with pytest.raises(ValueError) as context_manager:
+        Table(Store(), TableType(Type.FUNC_REF, 3, 3), [None, None])
+
+    exception = context_manager.value
+    assert str(exception) == 'The table has 3 initial element(s), but 2 element(s) are given'
+
+def test_constructor_with_an_invalid_element():
+    with pytest.raises(TypeError) as context_manager:
+        Table(Store(), TableType(Type.FUNC_REF, 1, 1), [42])
+
+    exception = context_manager.value
+    assert str(exception) == 'A table element must be a `Function`, a `Value` or `None`, given `int`'
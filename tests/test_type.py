@@ -1,4 +1,4 @@
-from wasmer import Type
+from wasmer import ExportKind, FunctionType, Module, Store, Type
 from enum import IntEnum
 
 def test_type():
@@ -11,3 +11,71 @@ def test_type():
     assert Type.V128 == 5
     assert Type.EXTERN_REF == 6
     assert Type.FUNC_REF == 7
+
+def test_export_kind():
+    assert issubclass(ExportKind, IntEnum)
+    assert len(ExportKind) == 4
+    assert ExportKind.FUNCTION == 1
+    assert ExportKind.GLOBAL == 2
+    assert ExportKind.MEMORY == 3
+    assert ExportKind.TABLE == 4
+
+def test_type_is_number():
+    assert [type_ for type_ in Type if type_.is_number()] == [Type.I32, Type.I64, Type.F32, Type.F64, Type.V128]
+
+def test_type_is_reference():
+    assert [type_ for type_ in Type if type_.is_reference()] == [Type.EXTERN_REF, Type.FUNC_REF]
+
+def test_type_predicates_on_function_type_params():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "f") (param i32 f32) (result f64)
+            f64.const 0))
+        """
+    )
+    function_type = module.exports[0].type
+    (i32, f32) = function_type.params
+
+    assert i32.is_number() and f32.is_number()
+    assert not i32.is_reference() and not f32.is_reference()
+    assert (i32.byte_size, f32.byte_size) == (4, 4)
+    assert function_type.results[0].byte_size == 8
+
+def test_type_repr():
+    assert repr(Type.I32) == 'Type.I32'
+    assert repr(Type.EXTERN_REF) == 'Type.EXTERN_REF'
+    assert repr([Type.I32, Type.F64]) == '[Type.I32, Type.F64]'
+    assert [eval(repr(type_)) for type_ in Type] == list(Type)
+
+def test_function_type_params_and_results_are_type_members():
+    function_type = FunctionType([Type.I32, Type.F64], [Type.I64])
+
+    assert repr(function_type.params) == '[Type.I32, Type.F64]'
+    assert repr(function_type.results) == '[Type.I64]'
+    assert all(isinstance(type_, Type) for type_ in function_type.params)
+
+def test_type_byte_size():
+    assert {type_: type_.byte_size for type_ in Type} == {
+        Type.I32: 4,
+        Type.I64: 8,
+        Type.F32: 4,
+        Type.F64: 8,
+        Type.V128: 16,
+        Type.EXTERN_REF: None,
+        Type.FUNC_REF: None,
+    }
+
+def test_function_type_arity():
+    function_type = FunctionType([Type.I32, Type.I64, Type.F32], [Type.F64])
+
+    assert (function_type.arity, function_type.result_arity) == (3, 1)
+    assert (function_type.is_nullary, function_type.is_void) == (False, False)
+
+def test_function_type_arity_of_nullary_and_void_functions():
+    function_type = FunctionType([], [])
+
+    assert (function_type.arity, function_type.result_arity) == (0, 0)
+    assert (function_type.is_nullary, function_type.is_void) == (True, True)
+    assert FunctionType([], [Type.I32, Type.I32]).result_arity == 2
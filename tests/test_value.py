@@ -1,4 +1,4 @@
-from wasmer import Value
+from wasmer import Store, Table, TableType, Value, Type
 import inspect
 import pytest
 
@@ -29,3 +29,32 @@ def test_f64_auto_cast():
 
 def test_v128():
     assert repr(Value.v128(340282366920938463463374607431768211455)) == 'V128(340282366920938463463374607431768211455)'
+
+def test_null():
+    value = Value.null()
+
+    assert repr(value) == 'ExternRef(null)'
+    assert value.is_null == True
+    assert bool(value) == False
+
+def test_null_funcref():
+    # Wasmer represents a null `funcref` as a null `externref`, e.g.
+    # to initialize the elements of a `funcref` table.
+    value = Value.null()
+    table = Table(Store(), TableType(Type.FUNC_REF, 1, None), value)
+
+    assert table.size == 1
+    assert value.is_null == True
+    assert bool(value) == False
+
+def test_numbers_are_not_null():
+    for value in [Value.i32(0), Value.i64(0), Value.f32(0), Value.f64(0), Value.v128(0)]:
+        assert value.is_null == False
+        assert bool(value) == True
+
+def test_type_and_value():
+    assert (Value.i32(42).type, Value.i32(42).value) == (Type.I32, 42)
+    assert (Value.i64(-42).type, Value.i64(-42).value) == (Type.I64, -42)
+    assert (Value.f64(4.2).type, Value.f64(4.2).value) == (Type.F64, 4.2)
+    assert (Value.v128(42).type, Value.v128(42).value) == (Type.V128, 42)
+    assert (Value.null().type, Value.null().value) == (Type.EXTERN_REF, None)
@@ -63,3 +63,129 @@ def test_wasi():
 Found 1 arguments: --foo\n\
 Found 2 environment variables: ABC=DEF, X=YZ\n\
 Found 1 preopened directories: DirEntry("/the_host_current_dir")\n'
+
+def test_wasi_run():
+    wasi_env = wasi.StateBuilder("test-program").argument("--foo").finalize()
+
+    assert wasi_env.run(Module(Store(), TEST_BYTES)) == 0
+
+def test_wasi_run_with_exit_code():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+          (memory (export "memory") 1)
+          (func (export "_start")
+            i32.const 7
+            call $proc_exit))
+        """
+    )
+
+    assert wasi.StateBuilder("test-program").finalize().run(module) == 7
+
+def test_wasi_run_not_a_wasi_module():
+    with pytest.raises(ValueError):
+        wasi.StateBuilder("test-program").finalize().run(Module(Store(), '(module)'))
+
+def test_wasi_instantiate():
+    module = Module(Store(), TEST_BYTES)
+    instance = wasi.StateBuilder("test-program").finalize().instantiate(module)
+
+    assert isinstance(instance, Instance)
+    assert hasattr(instance.exports, '_start')
+
+def test_wasi_instantiate_with_wasi_version():
+    module = Module(Store(), TEST_BYTES)
+    wasi_env = wasi.StateBuilder("test-program").finalize()
+
+    assert isinstance(wasi_env.instantiate(module, wasi.Version.SNAPSHOT1), Instance)
+
+    with pytest.raises(ValueError) as context_manager:
+        wasi_env.instantiate(module, wasi.Version.SNAPSHOT0)
+
+    exception = context_manager.value
+    assert str(exception) == (
+        'The module requires the WASI version `SNAPSHOT1`, but `SNAPSHOT0` was given'
+    )
+
+def test_wasi_instantiate_not_a_wasi_module():
+    with pytest.raises(ValueError) as context_manager:
+        wasi.StateBuilder("test-program").finalize().instantiate(Module(Store(), '(module)'))
+
+    exception = context_manager.value
+    assert str(exception) == 'The module is not a WASI module, no WASI version found'
+
+def test_wasi_exit():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+          (memory (export "memory") 1)
+          (func (export "_start")
+            i32.const 0
+            call $proc_exit))
+        """
+    )
+    import_object = wasi.StateBuilder("test-program").finalize().generate_import_object(store, wasi.Version.SNAPSHOT1)
+    instance = Instance(module, import_object)
+
+    with pytest.raises(wasi.WasiExit) as context_manager:
+        instance.exports._start()
+
+    exception = context_manager.value
+    assert exception.code == 0
+    assert not isinstance(exception, RuntimeError)
+    assert str(exception) == 'WASI exited with code: 0'
+
+# Writes `hello\n` twice to the standard output, and exits with the
+# error code of the last write.
+WRITING_MODULE = """
+(module
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "\\10\\00\\00\\00\\06\\00\\00\\00")
+  (data (i32.const 16) "hello\\n")
+  (func (export "_start")
+    (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))
+    (call $proc_exit
+      (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))))
+"""
+
+def test_wasi_stdout_callback():
+    chunks = []
+    wasi_env = wasi.StateBuilder("test-program").stdout(chunks.append).finalize()
+
+    assert wasi_env.run(Module(Store(), WRITING_MODULE)) == 0
+    assert chunks == [b'hello\n', b'hello\n']
+
+def test_wasi_stdout_callback_is_called_during_the_writes():
+    store = Store()
+    chunks = []
+    wasi_env = wasi.StateBuilder("test-program").stdout(chunks.append).finalize()
+    instance = wasi_env.instantiate(Module(store, WRITING_MODULE))
+
+    with pytest.raises(wasi.WasiExit):
+        instance.exports._start()
+
+    assert chunks == [b'hello\n', b'hello\n']
+
+def test_wasi_stdout_callback_raising():
+    def callback(chunk):
+        raise ValueError('boom')
+
+    wasi_env = wasi.StateBuilder("test-program").stdout(callback).finalize()
+
+    # The write fails in the program.
+    assert wasi_env.run(Module(Store(), WRITING_MODULE)) != 0
+
+def test_wasi_stdout_callback_not_callable():
+    with pytest.raises(ValueError) as context_manager:
+        wasi.StateBuilder("test-program").stdout(42)
+
+    exception = context_manager.value
+    assert str(exception) == 'The stdout callback must be a callable'